@@ -0,0 +1,498 @@
+//! Response cache shared by synchronous and asynchronous Giphy API clients
+//!
+//! A [`ResponseCache`] can be attached to both `SyncApi` and `AsyncApi` using `with_cache`.
+//! Responses are cached by the endpoint URL and the query string, excluding the API key,
+//! for the time-to-live specified by each request type. Trending results are cached for
+//! 10 minutes and GIFs retrieved by id for 24 hours, other requests are not cached unless
+//! configured otherwise using [`ResponseCache::with_ttl`].
+//!
+//! Responses can be kept in memory using [`MemoryStore`], or on disk using [`DiskStore`],
+//! so that multiple processes can share the results across restarts.
+//!
+//! ```no_run
+//! use giphy::cache::ResponseCache;
+//! use giphy::v1::sync::*;
+//! use std::time::Duration;
+//!
+//! let cache = ResponseCache::on_disk("/tmp/giphy-cache", 1000)
+//!     .expect("Cache directory could not be created")
+//!     .with_ttl("v1/gifs/search", Some(Duration::from_secs(300)));
+//!
+//! let client = reqwest::blocking::Client::new();
+//! let api = SyncApi::new("[your Giphy API key]".to_string(), client).with_cache(cache);
+//! ```
+//!
+//! [`ResponseCache`]: struct.ResponseCache.html
+//! [`ResponseCache::with_ttl`]: struct.ResponseCache.html#method.with_ttl
+//! [`MemoryStore`]: struct.MemoryStore.html
+//! [`DiskStore`]: struct.DiskStore.html
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(any(feature = "sync", feature = "async"))]
+use serde::de::DeserializeOwned;
+
+#[cfg(any(feature = "sync", feature = "async"))]
+use crate::error::{handle_response, GiphyError};
+use crate::transport::HttpRequest;
+#[cfg(any(feature = "sync", feature = "async"))]
+use crate::transport::HttpResponse;
+
+/// Response body stored in a [`CacheStore`]
+///
+/// [`CacheStore`]: trait.CacheStore.html
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub body: String,
+
+    /// Unix timestamp, in seconds, after which the response is stale
+    pub expires_at: u64,
+}
+
+impl CachedResponse {
+    fn new(body: String, ttl: Duration) -> CachedResponse {
+        CachedResponse {
+            body,
+            expires_at: unix_now().saturating_add(ttl.as_secs()),
+        }
+    }
+
+    fn is_fresh(&self) -> bool {
+        unix_now() < self.expires_at
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Storage backend of a [`ResponseCache`]
+///
+/// [`ResponseCache`]: struct.ResponseCache.html
+pub trait CacheStore: Send + Sync {
+    /// Returns the response stored under given key, whether it's fresh or not
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    /// Stores the response under given key, evicting other responses if needed
+    fn put(&self, key: &str, response: CachedResponse);
+
+    /// Removes the response stored under given key
+    fn remove(&self, key: &str);
+}
+
+/// In-memory [`CacheStore`] holding up to a fixed number of least recently used responses
+///
+/// [`CacheStore`]: trait.CacheStore.html
+pub struct MemoryStore {
+    capacity: usize,
+    state: Mutex<MemoryState>,
+}
+
+#[derive(Default)]
+struct MemoryState {
+    entries: HashMap<String, (CachedResponse, u64)>,
+    clock: u64,
+}
+
+impl MemoryStore {
+    /// Creates a store holding up to `capacity` responses
+    pub fn new(capacity: usize) -> MemoryStore {
+        MemoryStore {
+            capacity: capacity.max(1),
+            state: Mutex::new(MemoryState::default()),
+        }
+    }
+
+    /// Returns the number of stored responses
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    /// Returns `true` if there are no stored responses
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl CacheStore for MemoryStore {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut state = self.state.lock().unwrap();
+        state.clock += 1;
+        let clock = state.clock;
+
+        let (response, last_used) = state.entries.get_mut(key)?;
+        *last_used = clock;
+        Some(response.clone())
+    }
+
+    fn put(&self, key: &str, response: CachedResponse) {
+        let mut state = self.state.lock().unwrap();
+        state.clock += 1;
+        let clock = state.clock;
+
+        state.entries.insert(key.to_string(), (response, clock));
+        while state.entries.len() > self.capacity {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(key) => state.entries.remove(&key),
+                None => break,
+            };
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        self.state.lock().unwrap().entries.remove(key);
+    }
+}
+
+/// On-disk [`CacheStore`] keeping each response in a separate file in given directory
+///
+/// The store holds up to a fixed number of least recently used responses, using file
+/// modification times to track usage. Multiple processes can share the same directory.
+///
+/// [`CacheStore`]: trait.CacheStore.html
+pub struct DiskStore {
+    dir: PathBuf,
+    max_entries: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    key: String,
+    #[serde(flatten)]
+    response: CachedResponse,
+}
+
+impl DiskStore {
+    /// Creates a store in given directory, creating the directory if it does not exist
+    pub fn new<P: AsRef<Path>>(dir: P, max_entries: usize) -> io::Result<DiskStore> {
+        fs::create_dir_all(dir.as_ref())?;
+        Ok(DiskStore {
+            dir: dir.as_ref().to_path_buf(),
+            max_entries: max_entries.max(1),
+        })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        // FNV-1a, stable across Rust versions unlike `DefaultHasher`
+        let hash = key.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        self.dir.join(format!("{:016x}.json", hash))
+    }
+
+    fn evict(&self) -> io::Result<()> {
+        let mut entries = fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+            .collect::<Vec<_>>();
+
+        if entries.len() > self.max_entries {
+            entries.sort();
+            let excess = entries.len() - self.max_entries;
+            for (_, path) in entries.into_iter().take(excess) {
+                let _ = fs::remove_file(path);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CacheStore for DiskStore {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let path = self.path(key);
+        let entry: DiskEntry = serde_json::from_slice(&fs::read(&path).ok()?).ok()?;
+        if entry.key != key {
+            return None;
+        }
+
+        if let Ok(file) = fs::File::options().append(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+        Some(entry.response)
+    }
+
+    fn put(&self, key: &str, response: CachedResponse) {
+        let entry = DiskEntry {
+            key: key.to_string(),
+            response,
+        };
+        let path = self.path(key);
+        // Unique per write, so concurrent writers of the same key never share a temporary file
+        let tmp = path.with_extension(format!("{}.{:016x}.tmp", process::id(), fastrand::u64(..)));
+
+        // Write to a temporary file first, so concurrent readers never see partial entries
+        let written = serde_json::to_vec(&entry)
+            .map_err(io::Error::from)
+            .and_then(|data| fs::write(&tmp, data))
+            .and_then(|_| fs::rename(&tmp, &path));
+        match written {
+            Ok(()) => {
+                let _ = self.evict();
+            }
+            Err(_) => {
+                let _ = fs::remove_file(&tmp);
+            }
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = fs::remove_file(self.path(key));
+    }
+}
+
+/// Cache of Giphy API responses, attachable to both `SyncApi` and `AsyncApi`
+///
+/// Clones of the cache share the same storage.
+#[derive(Clone)]
+pub struct ResponseCache {
+    store: Arc<dyn CacheStore>,
+    ttl_overrides: Vec<(String, Option<Duration>)>,
+}
+
+impl ResponseCache {
+    /// Creates a cache using given storage backend
+    pub fn new<S: CacheStore + 'static>(store: S) -> ResponseCache {
+        ResponseCache {
+            store: Arc::new(store),
+            ttl_overrides: Vec::new(),
+        }
+    }
+
+    /// Creates a cache holding up to `capacity` responses in memory
+    pub fn in_memory(capacity: usize) -> ResponseCache {
+        ResponseCache::new(MemoryStore::new(capacity))
+    }
+
+    /// Creates a cache holding up to `max_entries` responses in given directory
+    pub fn on_disk<P: AsRef<Path>>(dir: P, max_entries: usize) -> io::Result<ResponseCache> {
+        Ok(ResponseCache::new(DiskStore::new(dir, max_entries)?))
+    }
+
+    /// Overrides the time-to-live of responses from endpoints starting with `endpoint_prefix`
+    ///
+    /// `None` disables caching of the matching endpoints. When multiple prefixes match an
+    /// endpoint, the longest one is used. For example, `"v1/gifs/"` matches GIFs retrieved
+    /// by id, but `"v1/gifs/search"` takes precedence for search requests.
+    pub fn with_ttl(mut self, endpoint_prefix: &str, ttl: Option<Duration>) -> Self {
+        self.ttl_overrides.push((endpoint_prefix.to_string(), ttl));
+        self.ttl_overrides
+            .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        self
+    }
+
+    /// Returns the time-to-live for responses from given endpoint
+    pub(crate) fn ttl_for(&self, endpoint: &str, default: Option<Duration>) -> Option<Duration> {
+        self.ttl_overrides
+            .iter()
+            .find(|(prefix, _)| endpoint.starts_with(prefix.as_str()))
+            .map_or(default, |(_, ttl)| *ttl)
+            .filter(|ttl| !ttl.is_zero())
+    }
+
+    /// Returns a fresh cached response body for given request
+    pub(crate) fn get(&self, request: &HttpRequest) -> Option<String> {
+        let key = cache_key(request);
+        let response = self.store.get(&key)?;
+        if response.is_fresh() {
+            Some(response.body)
+        } else {
+            self.store.remove(&key);
+            None
+        }
+    }
+
+    /// Stores the response body for given request
+    pub(crate) fn put(&self, request: &HttpRequest, body: String, ttl: Duration) {
+        self.store
+            .put(&cache_key(request), CachedResponse::new(body, ttl));
+    }
+
+    /// Handles the response to given request, storing its body if it's successful
+    #[cfg(any(feature = "sync", feature = "async"))]
+    pub(crate) fn handle_response<R: DeserializeOwned>(
+        &self,
+        request: &HttpRequest,
+        response: HttpResponse,
+        ttl: Duration,
+    ) -> Result<R, GiphyError> {
+        let body = response.body.clone();
+        let result = handle_response(response);
+        if result.is_ok() {
+            self.put(request, body, ttl);
+        }
+        result
+    }
+}
+
+/// Builds the cache key from the request URL and query, leaving out the API key
+fn cache_key(request: &HttpRequest) -> String {
+    let query = request
+        .query
+        .iter()
+        .filter(|(name, _)| name != "api_key")
+        .collect::<Vec<_>>();
+    let query = serde_urlencoded::to_string(query).unwrap_or_default();
+
+    format!("{}?{}", request.url, query)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transport::Method;
+    use std::env;
+
+    fn request(api_key: &str, q: &str) -> HttpRequest {
+        HttpRequest {
+            method: Method::Get,
+            url: "https://api.giphy.com/v1/gifs/search".to_string(),
+            query: vec![
+                ("api_key".to_string(), api_key.to_string()),
+                ("q".to_string(), q.to_string()),
+            ],
+            body: None,
+        }
+    }
+
+    #[test]
+    fn key_excludes_api_key() {
+        assert_eq!(
+            cache_key(&request("first", "cats")),
+            "https://api.giphy.com/v1/gifs/search?q=cats"
+        );
+        assert_eq!(
+            cache_key(&request("first", "cats")),
+            cache_key(&request("second", "cats"))
+        );
+        assert_ne!(
+            cache_key(&request("first", "cats")),
+            cache_key(&request("first", "dogs"))
+        );
+    }
+
+    #[test]
+    fn ttl_overrides() {
+        let day = Duration::from_secs(86400);
+        let cache = ResponseCache::in_memory(10)
+            .with_ttl("v1/gifs/", Some(day))
+            .with_ttl("v1/gifs/search", None);
+
+        assert_eq!(cache.ttl_for("v1/gifs/xT4uQulxzV39haRFjG", None), Some(day));
+        assert_eq!(cache.ttl_for("v1/gifs/search", Some(day)), None);
+        assert_eq!(cache.ttl_for("v1/stickers/trending", Some(day)), Some(day));
+        assert_eq!(cache.ttl_for("v1/stickers/random", None), None);
+    }
+
+    #[test]
+    fn memory_store_evicts_least_recently_used() {
+        let store = MemoryStore::new(2);
+        let response = CachedResponse::new("{}".to_string(), Duration::from_secs(60));
+
+        store.put("a", response.clone());
+        store.put("b", response.clone());
+        assert!(store.get("a").is_some());
+        store.put("c", response);
+
+        assert_eq!(store.len(), 2);
+        assert!(store.get("a").is_some());
+        assert!(store.get("b").is_none());
+        assert!(store.get("c").is_some());
+    }
+
+    #[test]
+    fn expired_responses_are_dropped() {
+        let cache = ResponseCache::in_memory(10);
+        let request = request("key", "cats");
+
+        cache.put(&request, "{}".to_string(), Duration::from_secs(0));
+        assert_eq!(cache.get(&request), None);
+
+        cache.put(&request, "{}".to_string(), Duration::from_secs(60));
+        assert_eq!(cache.get(&request), Some("{}".to_string()));
+    }
+
+    #[test]
+    fn disk_store_survives_restarts() {
+        let dir = env::temp_dir().join(format!("giphy-cache-test-{}", std::process::id()));
+        let request = request("key", "cats");
+
+        let cache = ResponseCache::on_disk(&dir, 10).unwrap();
+        cache.put(
+            &request,
+            "{\"data\":[]}".to_string(),
+            Duration::from_secs(60),
+        );
+        drop(cache);
+
+        let cache = ResponseCache::on_disk(&dir, 10).unwrap();
+        assert_eq!(cache.get(&request), Some("{\"data\":[]}".to_string()));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn disk_store_evicts_least_recently_used() {
+        let dir = env::temp_dir().join(format!("giphy-cache-evict-test-{}", std::process::id()));
+        let store = DiskStore::new(&dir, 2).unwrap();
+        let response = CachedResponse::new("{}".to_string(), Duration::from_secs(60));
+
+        store.put("a", response.clone());
+        std::thread::sleep(Duration::from_millis(20));
+        store.put("b", response.clone());
+        std::thread::sleep(Duration::from_millis(20));
+        store.put("c", response);
+
+        assert!(store.get("a").is_none());
+        assert!(store.get("b").is_some());
+        assert!(store.get("c").is_some());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn disk_store_concurrent_writers() {
+        let dir = env::temp_dir().join(format!("giphy-cache-writers-test-{}", std::process::id()));
+        let store = Arc::new(DiskStore::new(&dir, 10).unwrap());
+
+        let writers: Vec<_> = (0..8)
+            .map(|writer| {
+                let store = store.clone();
+                std::thread::spawn(move || {
+                    for i in 0..20 {
+                        let body = format!("{{\"writer\":{},\"i\":{}}}", writer, i);
+                        store.put("a", CachedResponse::new(body, Duration::from_secs(60)));
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let body = store.get("a").unwrap().body;
+        assert!(serde_json::from_str::<serde_json::Value>(&body).is_ok());
+        let files: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].extension().unwrap(), "json");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
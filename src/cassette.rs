@@ -0,0 +1,313 @@
+//! Record and replay of HTTP interactions for offline testing
+//!
+//! A [`Recorder`] wraps any [`Transport`] or [`AsyncTransport`], passes requests through and
+//! writes every request and response pair to a JSON cassette file. A [`Player`] loads the
+//! cassette and answers the same requests without network access. Both are transports
+//! themselves, so they plug into `SyncApi` and `AsyncApi` without changes to the code sending
+//! the requests. The API key is redacted from recorded requests and ignored when replaying.
+//!
+//! Requires `cassette` feature.
+//!
+//! ```no_run
+//! use giphy::cassette::{Player, Recorder};
+//! use giphy::v1::gifs::SearchRequest;
+//! use giphy::v1::sync::*;
+//!
+//! // Record once against Giphy API
+//! let client = reqwest::blocking::Client::new();
+//! let recorder = Recorder::new(client, "tests/cassettes/search.json");
+//! let api = SyncApi::new("[your Giphy API key]".to_string(), recorder);
+//! SearchRequest::new("tacos").send_to(&api).unwrap();
+//!
+//! // Replay in tests, any key will do
+//! let player = Player::load("tests/cassettes/search.json").unwrap();
+//! let api = SyncApi::new("test-api-key".to_string(), player);
+//! let response = SearchRequest::new("tacos").send_to(&api).unwrap();
+//! ```
+//!
+//! [`Recorder`]: struct.Recorder.html
+//! [`Player`]: struct.Player.html
+//! [`Transport`]: ../transport/trait.Transport.html
+//! [`AsyncTransport`]: ../transport/trait.AsyncTransport.html
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "async")]
+use futures::future::BoxFuture;
+
+#[cfg(feature = "async")]
+use crate::transport::AsyncTransport;
+#[cfg(feature = "sync")]
+use crate::transport::Transport;
+use crate::transport::{HttpRequest, HttpResponse, TransportError};
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Request and response pair stored in a cassette
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: HttpRequest,
+    pub response: HttpResponse,
+}
+
+fn redact(request: &HttpRequest) -> HttpRequest {
+    let query = request
+        .query
+        .iter()
+        .map(|(name, value)| match name.as_str() {
+            "api_key" => (name.clone(), REDACTED.to_string()),
+            _ => (name.clone(), value.clone()),
+        })
+        .collect();
+
+    HttpRequest {
+        method: request.method,
+        url: request.url.clone(),
+        query,
+        body: request.body.clone(),
+    }
+}
+
+/// Transport recording all interactions with the wrapped transport to a cassette file
+///
+/// The file is rewritten after every recorded interaction, so the cassette is complete
+/// even if the process does not exit cleanly. Transport errors are not recorded.
+pub struct Recorder<T> {
+    inner: T,
+    path: PathBuf,
+    interactions: Arc<Mutex<Vec<Interaction>>>,
+}
+
+impl<T> Recorder<T> {
+    /// Creates a recorder writing to given file, overwriting any previous recording
+    pub fn new<P: AsRef<Path>>(inner: T, path: P) -> Recorder<T> {
+        Recorder {
+            inner,
+            path: path.as_ref().to_path_buf(),
+            interactions: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Returns the interactions recorded so far
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.interactions.lock().unwrap().clone()
+    }
+}
+
+fn record(
+    path: &Path,
+    interactions: &Mutex<Vec<Interaction>>,
+    request: &HttpRequest,
+    response: &HttpResponse,
+) -> Result<(), TransportError> {
+    let mut interactions = interactions.lock().unwrap();
+    interactions.push(Interaction {
+        request: redact(request),
+        response: response.clone(),
+    });
+
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&*interactions)?)?;
+    Ok(())
+}
+
+#[cfg(feature = "sync")]
+impl<T: Transport> Transport for Recorder<T> {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, TransportError> {
+        let response = self.inner.send(request)?;
+        record(&self.path, &self.interactions, request, &response)?;
+        Ok(response)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T: AsyncTransport> AsyncTransport for Recorder<T> {
+    fn send(
+        &self,
+        request: &HttpRequest,
+    ) -> BoxFuture<'static, Result<HttpResponse, TransportError>> {
+        let response = self.inner.send(request);
+        let path = self.path.clone();
+        let interactions = self.interactions.clone();
+        let request = request.clone();
+
+        Box::pin(async move {
+            let response = response.await?;
+            record(&path, &interactions, &request, &response)?;
+            Ok(response)
+        })
+    }
+}
+
+/// Transport replaying interactions from a cassette file
+///
+/// Requests are matched by URL and query parameters, ignoring the API key. When the same
+/// request was recorded multiple times, the responses are replayed in the recorded order
+/// and the last one is repeated afterwards. Requests missing from the cassette fail with
+/// a transport error.
+#[derive(Clone)]
+pub struct Player {
+    interactions: Arc<Mutex<Vec<(Interaction, bool)>>>,
+}
+
+impl Player {
+    /// Loads interactions from given cassette file
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Player> {
+        let interactions: Vec<Interaction> = serde_json::from_slice(&fs::read(path)?)?;
+        Ok(Player::new(interactions))
+    }
+
+    /// Creates a player replaying given interactions
+    pub fn new(interactions: Vec<Interaction>) -> Player {
+        Player {
+            interactions: Arc::new(Mutex::new(
+                interactions.into_iter().map(|i| (i, false)).collect(),
+            )),
+        }
+    }
+
+    fn replay(&self, request: &HttpRequest) -> Result<HttpResponse, TransportError> {
+        let request = redact(request);
+        let mut interactions = self.interactions.lock().unwrap();
+
+        let mut matching = interactions
+            .iter_mut()
+            .filter(|(interaction, _)| interaction.request == request)
+            .peekable();
+        let mut last = None;
+        while let Some((interaction, replayed)) = matching.next() {
+            if !*replayed || matching.peek().is_none() {
+                *replayed = true;
+                last = Some(interaction.response.clone());
+                break;
+            }
+        }
+
+        last.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No recorded response for {}", request.url_with_query()),
+            )
+            .into()
+        })
+    }
+}
+
+#[cfg(feature = "sync")]
+impl Transport for Player {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, TransportError> {
+        self.replay(request)
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncTransport for Player {
+    fn send(
+        &self,
+        request: &HttpRequest,
+    ) -> BoxFuture<'static, Result<HttpResponse, TransportError>> {
+        Box::pin(futures::future::ready(self.replay(request)))
+    }
+}
+
+#[cfg(all(test, feature = "sync", feature = "reqwest"))]
+mod test {
+    use super::*;
+    use crate::transport::Method;
+    use crate::v1::gifs::{GetGifRequest, SearchRequest};
+    use crate::v1::sync::{RunnableSyncRequest, SyncApi};
+    use crate::v1::upload::UploadRequest;
+    use crate::GiphyError;
+    use mockito::{mock, server_url};
+    use std::env;
+
+    #[test]
+    fn record_then_replay() {
+        let path = env::temp_dir().join(format!("giphy-cassette-{}.json", std::process::id()));
+        let _m = mock("GET", "/v1/gifs/xT4uQulxzV39haRFjG?api_key=secret-key")
+            .with_status(200)
+            .with_body_from_file("data/example-get-gif-response.json")
+            .create();
+
+        let recorder = Recorder::new(reqwest::blocking::Client::new(), &path);
+        let api = SyncApi::new_with_url(server_url(), "secret-key".to_string(), recorder);
+        let recorded = GetGifRequest::new("xT4uQulxzV39haRFjG")
+            .send_to(&api)
+            .unwrap();
+
+        let cassette = fs::read_to_string(&path).unwrap();
+        assert!(!cassette.contains("secret-key"));
+        assert!(cassette.contains(REDACTED));
+
+        let player = Player::load(&path).unwrap();
+        let api = SyncApi::new_with_url(server_url(), "other-key".to_string(), player);
+        let replayed = GetGifRequest::new("xT4uQulxzV39haRFjG")
+            .send_to(&api)
+            .unwrap();
+        assert_eq!(replayed.data.id, recorded.data.id);
+
+        match SearchRequest::new("tacos").send_to(&api) {
+            Err(GiphyError::Transport(e)) => assert!(e.to_string().contains("tacos")),
+            r => panic!("Unexpected result: {:?}", r.map(|r| r.meta.status)),
+        }
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn record_then_replay_upload() {
+        let path =
+            env::temp_dir().join(format!("giphy-cassette-upload-{}.json", std::process::id()));
+        let _m = mock("POST", "/v1/gifs?api_key=secret-key")
+            .with_status(200)
+            .with_body_from_file("data/example-upload-response.json")
+            .create();
+        let upload =
+            || UploadRequest::from_bytes("tacos.gif", b"GIF89a".to_vec()).with_tags(vec!["tacos"]);
+
+        let recorder = Recorder::new(reqwest::blocking::Client::new(), &path);
+        let api = SyncApi::new_with_url(server_url(), "secret-key".to_string(), recorder);
+        let recorded = upload().send_to(&api).unwrap();
+
+        let player = Player::load(&path).unwrap();
+        let api = SyncApi::new_with_url(server_url(), "other-key".to_string(), player);
+        let replayed = upload().send_to(&api).unwrap();
+        assert_eq!(replayed.data.id, recorded.data.id);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn replay_in_recorded_order() {
+        let request = HttpRequest {
+            method: Method::Get,
+            url: "https://api.giphy.com/v1/gifs/random".to_string(),
+            query: vec![("api_key".to_string(), "key".to_string())],
+            body: None,
+        };
+        let response = |status| HttpResponse {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+        };
+        let player = Player::new(vec![
+            Interaction {
+                request: redact(&request),
+                response: response(503),
+            },
+            Interaction {
+                request: redact(&request),
+                response: response(200),
+            },
+        ]);
+
+        assert_eq!(player.replay(&request).unwrap().status, 503);
+        assert_eq!(player.replay(&request).unwrap().status, 200);
+        assert_eq!(player.replay(&request).unwrap().status, 200);
+    }
+}
@@ -0,0 +1,313 @@
+use serde::de::DeserializeOwned;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+#[cfg(any(feature = "sync", feature = "async"))]
+use crate::transport::HttpResponse;
+use crate::transport::TransportError;
+use crate::v1::Meta;
+
+/// Error returned when sending a request to Giphy API fails
+#[derive(Debug)]
+pub enum GiphyError {
+    /// The request could not be sent or the response could not be read
+    Transport(TransportError),
+
+    /// Giphy API responded with a non-success HTTP status, or with a [`Meta`] object
+    /// reporting a non-success status
+    ///
+    /// [`Meta`]: v1/struct.Meta.html
+    HttpStatus {
+        status: u16,
+        meta: Option<Meta>,
+        body: String,
+    },
+
+    /// The response body could not be deserialized into the expected response model
+    Deserialization {
+        /// JSON path of the value that failed to deserialize, e.g. `data[3].images.original`
+        path: String,
+        body: String,
+        source: serde_json::Error,
+    },
+
+    /// Giphy API rejected the request because the API key exceeded its quota, or the
+    /// client-side [`RateLimiter`] ran out of budget
+    ///
+    /// [`RateLimiter`]: struct.RateLimiter.html
+    RateLimited {
+        meta: Option<Meta>,
+        retry_after: Option<Duration>,
+    },
+
+    /// The request was rejected as invalid, either by Giphy API or before being sent
+    InvalidRequest {
+        reason: String,
+        /// HTTP status of the response, `None` if the request was rejected before being sent
+        status: Option<u16>,
+        meta: Option<Meta>,
+    },
+}
+
+/// Kind of a [`GiphyError`], without any of the data it carries
+///
+/// [`GiphyError`]: enum.GiphyError.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    Transport,
+    HttpStatus,
+    Deserialization,
+    RateLimited,
+    InvalidRequest,
+}
+
+impl GiphyError {
+    /// Returns the kind of the error
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            GiphyError::Transport(_) => ErrorKind::Transport,
+            GiphyError::HttpStatus { .. } => ErrorKind::HttpStatus,
+            GiphyError::Deserialization { .. } => ErrorKind::Deserialization,
+            GiphyError::RateLimited { .. } => ErrorKind::RateLimited,
+            GiphyError::InvalidRequest { .. } => ErrorKind::InvalidRequest,
+        }
+    }
+
+    /// Returns the HTTP status code associated with the error, if there is one
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            GiphyError::HttpStatus { status, .. } => Some(*status),
+            GiphyError::RateLimited { .. } => Some(429),
+            GiphyError::InvalidRequest { status, .. } => *status,
+            GiphyError::Transport(_) | GiphyError::Deserialization { .. } => None,
+        }
+    }
+
+    /// Returns the [`Meta`] object sent by Giphy API along with the error, if there is one
+    ///
+    /// [`Meta`]: v1/struct.Meta.html
+    pub fn meta(&self) -> Option<&Meta> {
+        match self {
+            GiphyError::HttpStatus { meta, .. }
+            | GiphyError::RateLimited { meta, .. }
+            | GiphyError::InvalidRequest { meta, .. } => meta.as_ref(),
+            GiphyError::Transport(_) | GiphyError::Deserialization { .. } => None,
+        }
+    }
+
+    /// Builds an error from a non-success HTTP response
+    pub(crate) fn from_status(status: u16, retry_after: Option<Duration>, body: String) -> Self {
+        let meta = parse_meta(&body);
+        match status {
+            429 => GiphyError::RateLimited { meta, retry_after },
+            400 => GiphyError::InvalidRequest {
+                reason: meta
+                    .as_ref()
+                    .map(|m| m.msg.clone())
+                    .unwrap_or_else(|| body.clone()),
+                status: Some(status),
+                meta,
+            },
+            _ => GiphyError::HttpStatus { status, meta, body },
+        }
+    }
+}
+
+impl fmt::Display for GiphyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GiphyError::Transport(e) => write!(f, "transport error: {}", e),
+            GiphyError::HttpStatus {
+                status,
+                meta: Some(meta),
+                ..
+            } => write!(f, "Giphy API returned status {}: {}", status, meta.msg),
+            GiphyError::HttpStatus { status, .. } => {
+                write!(f, "Giphy API returned status {}", status)
+            }
+            GiphyError::Deserialization { path, source, .. } => {
+                write!(
+                    f,
+                    "failed to deserialize response at `{}`: {}",
+                    path, source
+                )
+            }
+            GiphyError::RateLimited {
+                retry_after: Some(retry_after),
+                ..
+            } => write!(
+                f,
+                "Giphy API rate limit exceeded, retry after {}s",
+                retry_after.as_secs()
+            ),
+            GiphyError::RateLimited { .. } => write!(f, "Giphy API rate limit exceeded"),
+            GiphyError::InvalidRequest { reason, .. } => write!(f, "invalid request: {}", reason),
+        }
+    }
+}
+
+impl Error for GiphyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GiphyError::Transport(e) => Some(e.as_ref()),
+            GiphyError::Deserialization { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct MetaEnvelope {
+    meta: Option<Meta>,
+}
+
+fn parse_meta(body: &str) -> Option<Meta> {
+    serde_json::from_str::<MetaEnvelope>(body)
+        .ok()
+        .and_then(|envelope| envelope.meta)
+}
+
+/// Turns an HTTP response into the response model, or an error for non-success statuses
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(crate) fn handle_response<ResponseType>(
+    response: HttpResponse,
+) -> Result<ResponseType, GiphyError>
+where
+    ResponseType: DeserializeOwned,
+{
+    decode_response(check_status(response)?)
+}
+
+/// Turns responses with non-success HTTP statuses into errors, returning the body otherwise
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(crate) fn check_status(response: HttpResponse) -> Result<String, GiphyError> {
+    if !(200..300).contains(&response.status) {
+        // Only `Retry-After` given in seconds is supported
+        let retry_after = response
+            .header("retry-after")
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(Duration::from_secs);
+        return Err(GiphyError::from_status(
+            response.status,
+            retry_after,
+            response.body,
+        ));
+    }
+
+    Ok(response.body)
+}
+
+/// Deserializes a successful response body, reporting the JSON path of the first failure
+///
+/// Giphy API may report errors with a `200 OK` HTTP status and a non-200 status
+/// in the [`Meta`] object, these are turned into errors as well.
+///
+/// [`Meta`]: v1/struct.Meta.html
+pub(crate) fn decode_response<ResponseType>(body: String) -> Result<ResponseType, GiphyError>
+where
+    ResponseType: DeserializeOwned,
+{
+    if let Some(meta) = parse_meta(&body) {
+        if !(200..300).contains(&meta.status) {
+            return match u16::try_from(meta.status) {
+                Ok(status) => Err(GiphyError::from_status(status, None, body)),
+                Err(e) => Err(GiphyError::Deserialization {
+                    path: "meta.status".to_string(),
+                    source: serde::de::Error::custom(e),
+                    body,
+                }),
+            };
+        }
+    }
+
+    let deserializer = &mut serde_json::Deserializer::from_str(&body);
+    serde_path_to_error::deserialize(deserializer).map_err(|e| GiphyError::Deserialization {
+        path: e.path().to_string(),
+        source: e.into_inner(),
+        body,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::v1::SingleGifResponse;
+
+    #[test]
+    fn from_status_429_is_rate_limited() {
+        let body = r#"{"meta":{"msg":"Too Many Requests","status":429,"response_id":"abc"}}"#;
+        let err = GiphyError::from_status(429, Some(Duration::from_secs(30)), body.to_string());
+
+        match err {
+            GiphyError::RateLimited { meta, retry_after } => {
+                assert_eq!(meta.map(|m| m.status), Some(429));
+                assert_eq!(retry_after, Some(Duration::from_secs(30)));
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn from_status_400_is_invalid_request() {
+        let body = r#"{"meta":{"msg":"Bad Request","status":400,"response_id":"abc"}}"#;
+        let err = GiphyError::from_status(400, None, body.to_string());
+
+        match err {
+            GiphyError::InvalidRequest { reason, .. } => assert_eq!(reason, "Bad Request"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn from_status_400_without_meta() {
+        let err = GiphyError::from_status(400, None, "<html>Bad Request</html>".to_string());
+
+        assert_eq!(err.kind(), ErrorKind::InvalidRequest);
+        assert_eq!(err.status(), Some(400));
+        assert!(err.meta().is_none());
+    }
+
+    #[test]
+    fn from_status_without_meta() {
+        let body = r#"{"message":"Invalid authentication credentials"}"#;
+        let err = GiphyError::from_status(403, None, body.to_string());
+
+        assert_eq!(err.status(), Some(403));
+        assert!(err.meta().is_none());
+    }
+
+    #[test]
+    fn decode_response_reports_path() {
+        let body = r#"{"data":{"type":"gif","id":42},"meta":{"msg":"OK","status":200,"response_id":"abc"}}"#;
+
+        match decode_response::<SingleGifResponse>(body.to_string()) {
+            Err(GiphyError::Deserialization {
+                path, body: raw, ..
+            }) => {
+                assert_eq!(path, "data.id");
+                assert_eq!(raw, body);
+            }
+            r => panic!("Unexpected result: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn decode_response_checks_meta_status() {
+        let body = r#"{"data":[],"meta":{"msg":"Not Found","status":404,"response_id":"abc"}}"#;
+        let err = decode_response::<SingleGifResponse>(body.to_string()).unwrap_err();
+
+        assert_eq!(err.status(), Some(404));
+        assert_eq!(err.meta().map(|m| m.msg.as_str()), Some("Not Found"));
+    }
+
+    #[test]
+    fn decode_response_rejects_invalid_meta_status() {
+        let body = r#"{"data":[],"meta":{"msg":"Odd","status":65936,"response_id":"abc"}}"#;
+        match decode_response::<SingleGifResponse>(body.to_string()) {
+            Err(GiphyError::Deserialization { path, .. }) => assert_eq!(path, "meta.status"),
+            r => panic!("Unexpected result: {:?}", r.map(|r| r.meta.status)),
+        }
+    }
+}
@@ -7,7 +7,19 @@
 //! searching and retrieving GIFs from [Giphy] using `v1` API. Both synchronous
 //! and asynchronous styles are supported by the library.
 //!
-//! Stickers and posting GIFs to [Giphy] is currently not supported.
+//! Stickers can be searched and retrieved using request objects from [`giphy::v1::stickers`]
+//! module, which work with both synchronous and asynchronous API.
+//!
+//! Emoji from Giphy `v2` API can be listed using request objects from [`giphy::v2::emoji`]
+//! module, which are sent using the same clients.
+//!
+//! GIFs can be uploaded to [Giphy] using request objects from [`giphy::v1::upload`] module.
+//!
+//! Analytics pingbacks reporting when GIFs are shown, clicked and sent are batched and sent
+//! by reporters from [`giphy::v1::analytics`] module.
+//!
+//! Search results are personalized using random IDs issued by [`giphy::v1::randomid`] module,
+//! which are sent with every request made through a client `session`.
 //!
 //! ## Examples
 //! ### Synchronous API
@@ -59,6 +71,10 @@
 //! only difference is, [`send_to`] method will return a [`Future`] object when called
 //! with [`AsyncApi`].
 //!
+//! Retries and rate limiting wait using [`tokio`] timers, so with a `RetryPolicy` or a
+//! `RateLimiter` attached the futures must run on a Tokio runtime with the time driver
+//! enabled. Other runtimes can provide their own sleep function using `AsyncApi::with_sleep`.
+//!
 //! ```no_run
 //! # use giphy::v1::r#async::*;
 //! use giphy::v1::gifs::SearchRequest;
@@ -78,6 +94,26 @@
 //! }
 //! ```
 //!
+//! ### Error handling
+//! Both synchronous and asynchronous API report failures using [`GiphyError`], which
+//! distinguishes transport failures, non-success HTTP statuses, rate limiting, invalid requests
+//! and response bodies that could not be deserialized.
+//!
+//! ```no_run
+//! # use giphy::v1::sync::*;
+//! use giphy::v1::gifs::GetGifRequest;
+//! use giphy::GiphyError;
+//! # let client = reqwest::blocking::Client::new();
+//! # let api = SyncApi::new("[your Giphy API key]".to_string(), client);
+//!
+//! match GetGifRequest::new("xT4uQulxzV39haRFjG").send_to(&api) {
+//!     Ok(response) => println!("GIF: {:?}", response.data),
+//!     Err(GiphyError::HttpStatus { status: 404, .. }) => println!("GIF not found"),
+//!     Err(GiphyError::Deserialization { path, .. }) => println!("Unexpected value at {}", path),
+//!     Err(e) => panic!("Request failed: {}", e),
+//! }
+//! ```
+//!
 //! ### More examples
 //! See [examples] for showcase of all the currently possible [Giphy HTTP API]
 //! requests in both synchronous and asynchronous style.
@@ -97,13 +133,23 @@
 //! [`reqwest::async::Client`]: ../reqwest/async/struct.Client.html
 //! [`SyncApi`]: v1/sync/struct.SyncApi.html
 //! [`AsyncApi`]: v1/async/struct.AsyncApi.html
+//! [`GiphyError`]: enum.GiphyError.html
 //! [`giphy::v1::gifs`]: v1/gifs/index.html
+//! [`giphy::v1::stickers`]: v1/stickers/index.html
+//! [`giphy::v1::analytics`]: v1/analytics/index.html
+//! [`giphy::v1::randomid`]: v1/randomid/index.html
+//! [`giphy::v1::upload`]: v1/upload/index.html
+//! [`giphy::v2::emoji`]: v2/emoji/index.html
 //! [examples]: https://github.com/cfiet/giphy-rs/tree/master/examples
 //! [`tokio`]: ../tokio/index.html
 
+// Without `sync` or `async` only the models are usable, leaving the request plumbing unused
+#![cfg_attr(not(any(feature = "sync", feature = "async")), allow(dead_code))]
+
 #[cfg(feature = "async")]
 extern crate futures;
 
+#[cfg(feature = "reqwest")]
 extern crate reqwest;
 #[macro_use]
 extern crate serde_derive;
@@ -113,4 +159,18 @@ extern crate dotenv;
 #[cfg(test)]
 extern crate tokio;
 
+pub mod cache;
+#[cfg(feature = "cassette")]
+pub mod cassette;
+mod error;
+mod rate_limit;
+mod retry;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod transport;
 pub mod v1;
+pub mod v2;
+
+pub use error::{ErrorKind, GiphyError};
+pub use rate_limit::{RateLimitMode, RateLimiter};
+pub use retry::RetryPolicy;
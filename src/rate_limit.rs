@@ -0,0 +1,203 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[cfg(any(feature = "sync", feature = "async"))]
+use crate::error::GiphyError;
+
+/// What a [`RateLimiter`] does when there is no budget left for a request
+///
+/// [`RateLimiter`]: struct.RateLimiter.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateLimitMode {
+    /// Waits until the budget is replenished, sleeping in `SyncApi` and awaiting in `AsyncApi`
+    Wait,
+    /// Fails immediately with [`GiphyError::RateLimited`]
+    ///
+    /// [`GiphyError::RateLimited`]: enum.GiphyError.html#variant.RateLimited
+    FailFast,
+}
+
+/// Client-side token bucket rate limiter
+///
+/// Giphy API keys are limited to a fixed number of calls per hour. The limiter can be attached
+/// to both `SyncApi` and `AsyncApi` using `with_rate_limiter`, keeping the requests made using
+/// given API key within the quota. Clones of the limiter share the same budget, so the same
+/// limiter can be attached to multiple clients using the same key.
+///
+/// ```
+/// use giphy::{RateLimitMode, RateLimiter};
+///
+/// // Beta keys are limited to 100 calls per hour
+/// let limiter = RateLimiter::per_hour(100).with_mode(RateLimitMode::FailFast);
+/// assert_eq!(limiter.remaining(), 100);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+    mode: RateLimitMode,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: u32,
+    period: Duration,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        let rate = self.capacity as f64 / self.period.as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * rate).min(self.capacity as f64);
+        self.refilled_at = now;
+    }
+
+    #[cfg(any(feature = "sync", feature = "async"))]
+    fn time_until_available(&self) -> Duration {
+        let missing = 1.0 - self.tokens;
+        let rate = self.capacity as f64 / self.period.as_secs_f64();
+        Duration::from_secs_f64(missing / rate)
+    }
+}
+
+impl RateLimiter {
+    /// Creates a limiter allowing `calls` requests per `period`
+    ///
+    /// The budget starts full and is replenished continuously over the period.
+    pub fn new(calls: u32, period: Duration) -> RateLimiter {
+        let capacity = calls.max(1);
+        RateLimiter {
+            bucket: Arc::new(Mutex::new(Bucket {
+                capacity,
+                period,
+                tokens: capacity as f64,
+                refilled_at: Instant::now(),
+            })),
+            mode: RateLimitMode::Wait,
+        }
+    }
+
+    /// Creates a limiter allowing `calls` requests per hour
+    pub fn per_hour(calls: u32) -> RateLimiter {
+        RateLimiter::new(calls, Duration::from_secs(3600))
+    }
+
+    /// Specifies what the limiter does when there is no budget left
+    pub fn with_mode(mut self, mode: RateLimitMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns the maximum number of requests allowed per period
+    pub fn capacity(&self) -> u32 {
+        self.bucket.lock().unwrap().capacity
+    }
+
+    /// Returns the number of requests that can be made right now without waiting
+    pub fn remaining(&self) -> u32 {
+        let mut bucket = self.bucket.lock().unwrap();
+        bucket.refill();
+        bucket.tokens.floor() as u32
+    }
+
+    /// Takes a token from the bucket, or returns the time until one is available
+    #[cfg(any(feature = "sync", feature = "async"))]
+    fn try_acquire(&self) -> Result<(), Duration> {
+        let mut bucket = self.bucket.lock().unwrap();
+        bucket.refill();
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(bucket.time_until_available())
+        }
+    }
+
+    #[cfg(any(feature = "sync", feature = "async"))]
+    fn exhausted(&self, wait: Duration) -> Result<(), GiphyError> {
+        match self.mode {
+            RateLimitMode::FailFast => Err(GiphyError::RateLimited {
+                meta: None,
+                retry_after: Some(wait),
+            }),
+            RateLimitMode::Wait => Ok(()),
+        }
+    }
+
+    /// Takes a token from the bucket, blocking the current thread until one is available
+    #[cfg(feature = "sync")]
+    pub(crate) fn acquire_blocking(&self) -> Result<(), GiphyError> {
+        while let Err(wait) = self.try_acquire() {
+            self.exhausted(wait)?;
+            std::thread::sleep(wait);
+        }
+        Ok(())
+    }
+
+    /// Takes a token from the bucket, waiting asynchronously using `sleep` until one is available
+    #[cfg(feature = "async")]
+    pub(crate) async fn acquire(
+        &self,
+        sleep: &crate::v1::r#async::Sleep,
+    ) -> Result<(), GiphyError> {
+        while let Err(wait) = self.try_acquire() {
+            self.exhausted(wait)?;
+            sleep(wait).await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "sync"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn remaining_budget() {
+        let limiter = RateLimiter::per_hour(3);
+        assert_eq!(limiter.capacity(), 3);
+        assert_eq!(limiter.remaining(), 3);
+
+        limiter.acquire_blocking().unwrap();
+        limiter.acquire_blocking().unwrap();
+        assert_eq!(limiter.remaining(), 1);
+    }
+
+    #[test]
+    fn clones_share_budget() {
+        let limiter = RateLimiter::per_hour(2);
+        let clone = limiter.clone();
+
+        clone.acquire_blocking().unwrap();
+        assert_eq!(limiter.remaining(), 1);
+    }
+
+    #[test]
+    fn fail_fast_when_exhausted() {
+        let limiter = RateLimiter::per_hour(1).with_mode(RateLimitMode::FailFast);
+        limiter.acquire_blocking().unwrap();
+
+        match limiter.acquire_blocking() {
+            Err(GiphyError::RateLimited {
+                meta: None,
+                retry_after: Some(wait),
+            }) => assert!(wait > Duration::from_secs(3500)),
+            r => panic!("Unexpected result: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn wait_until_replenished() {
+        let limiter = RateLimiter::new(1, Duration::from_millis(50));
+        let start = Instant::now();
+
+        limiter.acquire_blocking().unwrap();
+        limiter.acquire_blocking().unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,261 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::{ErrorKind, GiphyError};
+
+/// Policy deciding whether and when failed requests are retried
+///
+/// The policy can be attached to both `SyncApi` and `AsyncApi` using `with_retry_policy`.
+/// By default requests are attempted up to 3 times, retrying on rate limiting, `5xx`
+/// statuses and transport errors, with exponential backoff starting at 500ms.
+///
+/// ```
+/// use giphy::{ErrorKind, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new()
+///     .with_max_attempts(5)
+///     .with_backoff(Duration::from_millis(200), Duration::from_secs(10))
+///     .retry_on_status(|status| status == 429 || status == 503)
+///     .retry_on_error_kind(|kind| kind == ErrorKind::Transport);
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: bool,
+    respect_retry_after: bool,
+    retry_status: Arc<dyn Fn(u16) -> bool + Send + Sync>,
+    retry_error_kind: Arc<dyn Fn(ErrorKind) -> bool + Send + Sync>,
+}
+
+impl RetryPolicy {
+    /// Creates a new retry policy with default settings
+    pub fn new() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: true,
+            respect_retry_after: true,
+            retry_status: Arc::new(|status| status == 429 || (500..600).contains(&status)),
+            retry_error_kind: Arc::new(|kind| kind == ErrorKind::Transport),
+        }
+    }
+
+    /// Specifies the maximum number of attempts, including the first one
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Specifies the delay before the first retry and the maximum delay between retries
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Specifies the factor the delay is multiplied by after each retry
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Enables or disables randomizing delays between retries
+    ///
+    /// With jitter enabled each delay is picked at random between half and the full
+    /// exponential backoff value, so that many clients don't retry in lockstep.
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Enables or disables waiting for the duration given in `Retry-After` header
+    /// instead of the exponential backoff
+    pub fn with_retry_after(mut self, respect_retry_after: bool) -> Self {
+        self.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    /// Specifies which HTTP statuses should be retried
+    ///
+    /// Used for errors carrying a status returned by Giphy API.
+    pub fn retry_on_status<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(u16) -> bool + Send + Sync + 'static,
+    {
+        self.retry_status = Arc::new(predicate);
+        self
+    }
+
+    /// Specifies which kinds of errors without an HTTP status should be retried
+    pub fn retry_on_error_kind<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(ErrorKind) -> bool + Send + Sync + 'static,
+    {
+        self.retry_error_kind = Arc::new(predicate);
+        self
+    }
+
+    /// Returns `true` if a request that failed with given error should be retried
+    pub fn should_retry(&self, error: &GiphyError) -> bool {
+        match error.status() {
+            Some(status) => (self.retry_status)(status),
+            None => (self.retry_error_kind)(error.kind()),
+        }
+    }
+
+    /// Returns the delay before the next attempt, or `None` if the request should not be retried
+    ///
+    /// `attempt` is the number of the attempt that failed, starting from 1.
+    pub(crate) fn retry_delay(&self, attempt: u32, error: &GiphyError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.should_retry(error) {
+            return None;
+        }
+
+        if let GiphyError::RateLimited {
+            retry_after: Some(retry_after),
+            ..
+        } = error
+        {
+            if self.respect_retry_after {
+                return Some(*retry_after);
+            }
+        }
+
+        // Compared in seconds first, since `Duration::mul_f64` panics on overflow
+        let factor = self.multiplier.powf(f64::from(attempt.saturating_sub(1)));
+        let backoff =
+            if self.initial_backoff.as_secs_f64() * factor < self.max_backoff.as_secs_f64() {
+                self.initial_backoff.mul_f64(factor)
+            } else {
+                self.max_backoff
+            };
+
+        if self.jitter {
+            Some(backoff.mul_f64(0.5 + fastrand::f64() * 0.5))
+        } else {
+            Some(backoff)
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new()
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("multiplier", &self.multiplier)
+            .field("jitter", &self.jitter)
+            .field("respect_retry_after", &self.respect_retry_after)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn status_error(status: u16) -> GiphyError {
+        GiphyError::from_status(status, None, String::new())
+    }
+
+    #[test]
+    fn exponential_backoff_without_jitter() {
+        let policy = RetryPolicy::new()
+            .with_max_attempts(5)
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(300))
+            .with_jitter(false);
+        let err = status_error(503);
+
+        assert_eq!(
+            policy.retry_delay(1, &err),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            policy.retry_delay(2, &err),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(
+            policy.retry_delay(3, &err),
+            Some(Duration::from_millis(300))
+        );
+        assert_eq!(
+            policy.retry_delay(4, &err),
+            Some(Duration::from_millis(300))
+        );
+        assert_eq!(policy.retry_delay(5, &err), None);
+    }
+
+    #[test]
+    fn backoff_capped_for_large_attempts() {
+        let policy = RetryPolicy::new()
+            .with_max_attempts(u32::MAX)
+            .with_jitter(false);
+        let err = status_error(503);
+
+        assert_eq!(policy.retry_delay(100, &err), Some(Duration::from_secs(30)));
+        assert_eq!(
+            policy.retry_delay(u32::MAX - 1, &err),
+            Some(Duration::from_secs(30))
+        );
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let policy =
+            RetryPolicy::new().with_backoff(Duration::from_secs(1), Duration::from_secs(1));
+        let err = status_error(500);
+
+        for _ in 0..100 {
+            let delay = policy.retry_delay(1, &err).unwrap();
+            assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn honors_retry_after() {
+        let err = GiphyError::from_status(429, Some(Duration::from_secs(7)), String::new());
+
+        let policy = RetryPolicy::new();
+        assert_eq!(policy.retry_delay(1, &err), Some(Duration::from_secs(7)));
+
+        let policy = RetryPolicy::new()
+            .with_retry_after(false)
+            .with_jitter(false);
+        assert_eq!(
+            policy.retry_delay(1, &err),
+            Some(Duration::from_millis(500))
+        );
+    }
+
+    #[test]
+    fn predicates() {
+        let policy = RetryPolicy::new();
+        assert!(policy.should_retry(&status_error(429)));
+        assert!(policy.should_retry(&status_error(502)));
+        assert!(!policy.should_retry(&status_error(404)));
+        assert!(!policy.should_retry(&status_error(400)));
+
+        let policy = RetryPolicy::new()
+            .retry_on_status(|status| status == 404)
+            .retry_on_error_kind(|kind| kind == ErrorKind::Deserialization);
+        assert!(policy.should_retry(&status_error(404)));
+        assert!(!policy.should_retry(&status_error(500)));
+
+        let err = crate::error::decode_response::<crate::v1::Meta>("{}".to_string()).unwrap_err();
+        assert!(policy.should_retry(&err));
+    }
+}
@@ -0,0 +1,567 @@
+//! In-process fake Giphy API server for integration tests
+//!
+//! [`FakeGiphy`] listens on a random local port and serves `v1` GIF and sticker endpoints
+//! (search, trending, translate, random, GIFs by id) from an in-memory corpus, seeded with
+//! the GIFs from the example responses in the repository `data` directory. Limit and offset
+//! pagination and rating filtering behave like in Giphy API, and errors or latency can be
+//! injected to test failure handling.
+//!
+//! Requires `testing` feature.
+//!
+//! ```no_run
+//! use giphy::testing::{Fault, FakeGiphy};
+//! use giphy::v1::gifs::SearchRequest;
+//! use giphy::v1::sync::*;
+//!
+//! let server = FakeGiphy::start();
+//! let client = reqwest::blocking::Client::new();
+//! let api = SyncApi::new_with_url(server.url(), "test-api-key".to_string(), client);
+//!
+//! let response = SearchRequest::new("rage").with_limit(2).send_to(&api).unwrap();
+//! assert_eq!(response.data.len(), 2);
+//!
+//! server.inject_fault(Fault::Status(503));
+//! assert!(SearchRequest::new("rage").send_to(&api).is_err());
+//! ```
+//!
+//! [`FakeGiphy`]: struct.FakeGiphy.html
+
+use serde_json::{json, Value};
+use std::collections::{HashSet, VecDeque};
+use std::io::{self, BufRead, BufReader, Write};
+use std::iter;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::v1::pagination::MAX_OFFSET;
+
+const FIXTURES: &[&str] = &[
+    include_str!("../data/example-search-response.json"),
+    include_str!("../data/example-trending-response.json"),
+    include_str!("../data/example-translate-response.json"),
+    include_str!("../data/example-random-response.json"),
+    include_str!("../data/example-get-gif-response.json"),
+    include_str!("../data/example-get-gifs-response.json"),
+    include_str!("../data/example-stickers-search-response.json"),
+    include_str!("../data/example-stickers-trending-response.json"),
+    include_str!("../data/example-stickers-translate-response.json"),
+    include_str!("../data/example-stickers-random-response.json"),
+];
+
+const DEFAULT_LIMIT: usize = 25;
+const MAX_LIMIT: usize = 50;
+
+/// Failure injected into responses of a [`FakeGiphy`] server
+///
+/// [`FakeGiphy`]: struct.FakeGiphy.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// Responds with given HTTP status and a Giphy error body
+    Status(u16),
+    /// Responds with `429 Too Many Requests`, with optional `Retry-After` header
+    RateLimited(Option<Duration>),
+    /// Responds with `200 OK` and a body that is not valid JSON
+    MalformedBody,
+    /// Closes the connection without responding
+    Disconnect,
+    /// Delays the response, which is otherwise served normally
+    Latency(Duration),
+}
+
+#[derive(Default)]
+struct State {
+    corpus: Vec<Value>,
+    faults: VecDeque<Fault>,
+    latency: Duration,
+    requests: usize,
+}
+
+/// Fake Giphy API server running on a background thread
+///
+/// The server is stopped when dropped.
+pub struct FakeGiphy {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FakeGiphy {
+    /// Starts a server with the corpus seeded from the example responses
+    pub fn start() -> FakeGiphy {
+        let mut seen = HashSet::new();
+        let corpus = FIXTURES
+            .iter()
+            .flat_map(|fixture| {
+                let response: Value = serde_json::from_str(fixture).unwrap();
+                match response["data"].clone() {
+                    Value::Array(items) => items,
+                    item => vec![item],
+                }
+            })
+            .filter(|item| seen.insert((item["type"].clone(), item["id"].clone())))
+            .collect();
+
+        FakeGiphy::with_corpus(corpus)
+    }
+
+    /// Starts a server serving given GIF and sticker objects
+    ///
+    /// Objects are served by sticker endpoints when their `type` is `"sticker"`, and by
+    /// GIF endpoints otherwise.
+    pub fn with_corpus(corpus: Vec<Value>) -> FakeGiphy {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind fake Giphy server");
+        let addr = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(State {
+            corpus,
+            ..State::default()
+        }));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let state = state.clone();
+            let shutdown = shutdown.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        let state = state.clone();
+                        thread::spawn(move || {
+                            let _ = handle_connection(stream, &state);
+                        });
+                    }
+                }
+            })
+        };
+
+        FakeGiphy {
+            addr,
+            state,
+            shutdown,
+            thread: Some(thread),
+        }
+    }
+
+    /// Returns the API root URL to pass to `new_with_url`
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Adds a GIF or sticker object to the corpus
+    pub fn add(&self, item: Value) {
+        self.state.lock().unwrap().corpus.push(item);
+    }
+
+    /// Injects a fault into the response to the next request
+    ///
+    /// Multiple faults are applied to subsequent requests in the order they were injected.
+    pub fn inject_fault(&self, fault: Fault) {
+        self.state.lock().unwrap().faults.push_back(fault);
+    }
+
+    /// Injects the same fault into the responses to given number of requests
+    pub fn inject_faults(&self, fault: Fault, count: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.faults.extend(iter::repeat(fault).take(count));
+    }
+
+    /// Delays all responses by given duration
+    pub fn set_latency(&self, latency: Duration) {
+        self.state.lock().unwrap().latency = latency;
+    }
+
+    /// Returns the number of requests received so far
+    pub fn request_count(&self) -> usize {
+        self.state.lock().unwrap().requests
+    }
+}
+
+impl Drop for FakeGiphy {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake up the listener blocked on accepting connections
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+fn handle_connection(stream: TcpStream, state: &Mutex<State>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let target = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query: Vec<(String, String)> = serde_urlencoded::from_str(query).unwrap_or_default();
+
+    let (fault, latency, response) = {
+        let mut state = state.lock().unwrap();
+        state.requests += 1;
+        let fault = state.faults.pop_front();
+        let response = route(&state.corpus, path, &query);
+        (fault, state.latency, response)
+    };
+
+    thread::sleep(latency);
+    let response = match fault {
+        None => response,
+        Some(Fault::Status(status)) => error(status, reason(status)),
+        Some(Fault::RateLimited(retry_after)) => {
+            let mut response = error(429, reason(429));
+            if let Some(retry_after) = retry_after {
+                response
+                    .headers
+                    .push(("Retry-After".to_string(), retry_after.as_secs().to_string()));
+            }
+            response
+        }
+        Some(Fault::MalformedBody) => Response {
+            status: 200,
+            headers: Vec::new(),
+            body: "{\"data\": [".to_string(),
+        },
+        Some(Fault::Disconnect) => return Ok(()),
+        Some(Fault::Latency(delay)) => {
+            thread::sleep(delay);
+            response
+        }
+    };
+
+    write_response(stream, response)
+}
+
+fn write_response(mut stream: TcpStream, response: Response) -> io::Result<()> {
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        reason(response.status),
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(response.body.as_bytes())?;
+    stream.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
+
+fn meta(status: u16, msg: &str) -> Value {
+    json!({
+        "msg": msg,
+        "status": status,
+        "response_id": format!("fake{:016x}", fastrand::u64(..)),
+    })
+}
+
+fn error(status: u16, msg: &str) -> Response {
+    Response {
+        status,
+        headers: Vec::new(),
+        body: json!({ "data": [], "meta": meta(status, msg) }).to_string(),
+    }
+}
+
+fn single(item: Value) -> Response {
+    Response {
+        status: 200,
+        headers: Vec::new(),
+        body: json!({ "data": item, "meta": meta(200, "OK") }).to_string(),
+    }
+}
+
+fn param<'q>(query: &'q [(String, String)], name: &str) -> Option<&'q str> {
+    query
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+fn route(corpus: &[Value], path: &str, query: &[(String, String)]) -> Response {
+    if param(query, "api_key").map_or(true, str::is_empty) {
+        return error(401, "No API key found in request.");
+    }
+
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let (kind, action) = match segments.as_slice() {
+        ["v1", "gifs"] => return get_many(corpus, query),
+        ["v1", kind @ ("gifs" | "stickers"), action] => (*kind, *action),
+        _ => return error(404, "Not Found"),
+    };
+
+    let rating = param(query, "rating");
+    let items: Vec<&Value> = corpus
+        .iter()
+        .filter(|item| (item["type"] == "sticker") == (kind == "stickers"))
+        .filter(|item| rating.map_or(true, |rating| rating_allowed(item, rating)))
+        .collect();
+
+    match action {
+        "search" => match param(query, "q") {
+            Some(q) => paginate(matching(&items, q), query),
+            None => error(400, "Missing required parameter: q"),
+        },
+        "trending" => paginate(items, query),
+        "translate" => match param(query, "s") {
+            Some(s) => match matching(&items, s).first().or(items.first()) {
+                Some(item) => single((*item).clone()),
+                None => error(404, "Not Found"),
+            },
+            None => error(400, "Missing required parameter: s"),
+        },
+        "random" => {
+            let tagged = match param(query, "tag") {
+                Some(tag) => matching(&items, tag),
+                None => items.clone(),
+            };
+            let candidates = if tagged.is_empty() { items } else { tagged };
+            match fastrand::choice(candidates) {
+                Some(item) => single(item.clone()),
+                None => error(404, "Not Found"),
+            }
+        }
+        id => match items.iter().find(|item| item["id"] == id) {
+            Some(item) => single((*item).clone()),
+            None => error(404, "Not Found"),
+        },
+    }
+}
+
+fn get_many(corpus: &[Value], query: &[(String, String)]) -> Response {
+    let ids = match param(query, "ids") {
+        Some(ids) => ids.split(',').collect::<Vec<_>>(),
+        None => return error(400, "Missing required parameter: ids"),
+    };
+    let items = ids
+        .iter()
+        .filter_map(|id| corpus.iter().find(|item| item["id"] == *id))
+        .collect();
+
+    paginate(items, &[])
+}
+
+/// Returns items with all words of the query in their title, slug or username
+fn matching<'c>(items: &[&'c Value], query: &str) -> Vec<&'c Value> {
+    let words: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    items
+        .iter()
+        .filter(|item| {
+            let text = ["title", "slug", "username"]
+                .iter()
+                .filter_map(|field| item[field].as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase();
+            words.iter().all(|word| text.contains(word.as_str()))
+        })
+        .copied()
+        .collect()
+}
+
+/// Giphy returns content rated at or below the requested rating
+fn rating_allowed(item: &Value, rating: &str) -> bool {
+    let level = |rating: &str| match rating.to_lowercase().as_str() {
+        "y" => 0,
+        "g" => 1,
+        "pg" => 2,
+        "pg-13" => 3,
+        _ => 4,
+    };
+    level(item["rating"].as_str().unwrap_or("g")) <= level(rating)
+}
+
+fn paginate(items: Vec<&Value>, query: &[(String, String)]) -> Response {
+    let number = |name: &str, default: usize| match param(query, name) {
+        Some(value) => value.parse::<usize>().map_err(|_| name.to_string()),
+        None => Ok(default),
+    };
+    let (limit, offset) = match (number("limit", DEFAULT_LIMIT), number("offset", 0)) {
+        (Ok(limit), Ok(offset)) => (limit.clamp(1, MAX_LIMIT), offset),
+        (Err(name), _) | (_, Err(name)) => {
+            return error(400, &format!("Invalid parameter: {}", name))
+        }
+    };
+    if offset > MAX_OFFSET as usize {
+        return error(400, "Invalid parameter: offset");
+    }
+
+    let page: Vec<&Value> = items.iter().skip(offset).take(limit).copied().collect();
+    Response {
+        status: 200,
+        headers: Vec::new(),
+        body: json!({
+            "data": page,
+            "pagination": {
+                "total_count": items.len(),
+                "count": page.len(),
+                "offset": offset,
+            },
+            "meta": meta(200, "OK"),
+        })
+        .to_string(),
+    }
+}
+
+#[cfg(all(test, feature = "sync", feature = "reqwest"))]
+mod test {
+    use super::*;
+    use crate::v1::gifs::{GetGifRequest, GetGifsRequest, SearchRequest, TrendingRequest};
+    use crate::v1::sync::{RunnableSyncRequest, SyncApi};
+    use crate::v1::Rating;
+    use crate::{GiphyError, RetryPolicy};
+
+    fn api(server: &FakeGiphy) -> SyncApi {
+        let client = reqwest::blocking::Client::new();
+        SyncApi::new_with_url(server.url(), "test-api-key".to_string(), client)
+    }
+
+    #[test]
+    fn search_and_paginate() {
+        let server = FakeGiphy::start();
+        let api = api(&server);
+
+        let response = SearchRequest::new("rage")
+            .with_limit(2)
+            .with_offset(1)
+            .send_to(&api)
+            .unwrap();
+        assert_eq!(response.data.len(), 2);
+        assert_eq!(response.pagination.offset, 1);
+        assert_eq!(response.pagination.total_count, Some(4));
+
+        let ids = api
+            .paginate(SearchRequest::new("rage").with_limit(1))
+            .map(|gif| gif.unwrap().id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids.len(), 4);
+        assert_eq!(server.request_count(), 5);
+    }
+
+    #[test]
+    fn rating_filter() {
+        let server = FakeGiphy::start();
+        let api = api(&server);
+
+        let mut gif = server.state.lock().unwrap().corpus[0].clone();
+        gif["id"] = "rated-r".into();
+        gif["rating"] = "r".into();
+        server.add(gif);
+
+        let ids = |rating| {
+            api.paginate(TrendingRequest::new().with_rating(rating))
+                .map(|gif| gif.unwrap().id)
+                .collect::<Vec<_>>()
+        };
+        let all = ids(Rating::R);
+        let general = ids(Rating::G);
+        assert!(all.contains(&"rated-r".to_string()));
+        assert!(!general.contains(&"rated-r".to_string()));
+        assert!(!general.contains(&"xT4uQulxzV39haRFjG".to_string()));
+        assert!(all.contains(&"xT4uQulxzV39haRFjG".to_string()));
+    }
+
+    #[test]
+    fn get_by_id() {
+        let server = FakeGiphy::start();
+        let api = api(&server);
+
+        let response = GetGifRequest::new("xT4uQulxzV39haRFjG")
+            .send_to(&api)
+            .unwrap();
+        assert_eq!(response.data.id, "xT4uQulxzV39haRFjG");
+
+        let err = GetGifRequest::new("missing").send_to(&api).unwrap_err();
+        assert_eq!(err.status(), Some(404));
+
+        let response = GetGifsRequest::new(vec!["xT4uQulxzV39haRFjG", "missing"])
+            .send_to(&api)
+            .unwrap();
+        assert_eq!(response.data.len(), 1);
+    }
+
+    #[test]
+    fn injected_faults() {
+        let server = FakeGiphy::start();
+        let api = api(&server);
+        let request = GetGifRequest::new("xT4uQulxzV39haRFjG");
+
+        server.inject_fault(Fault::RateLimited(Some(Duration::from_secs(30))));
+        server.inject_fault(Fault::MalformedBody);
+        server.inject_fault(Fault::Disconnect);
+        match request.send_to(&api) {
+            Err(GiphyError::RateLimited {
+                retry_after: Some(retry_after),
+                ..
+            }) => assert_eq!(retry_after, Duration::from_secs(30)),
+            r => panic!("Unexpected result: {:?}", r.map(|r| r.meta.status)),
+        }
+        match request.send_to(&api) {
+            Err(GiphyError::Deserialization { .. }) => {}
+            r => panic!("Unexpected result: {:?}", r.map(|r| r.meta.status)),
+        }
+        match request.send_to(&api) {
+            Err(GiphyError::Transport(_)) => {}
+            r => panic!("Unexpected result: {:?}", r.map(|r| r.meta.status)),
+        }
+
+        server.inject_faults(Fault::Status(503), 2);
+        let api = api.with_retry_policy(
+            RetryPolicy::new().with_backoff(Duration::from_millis(1), Duration::from_millis(1)),
+        );
+        assert!(request.send_to(&api).is_ok());
+        assert_eq!(server.request_count(), 6);
+    }
+
+    #[test]
+    fn injected_latency() {
+        let server = FakeGiphy::start();
+        let api = api(&server);
+        let request = GetGifRequest::new("xT4uQulxzV39haRFjG");
+
+        server.set_latency(Duration::from_millis(20));
+        server.inject_fault(Fault::Latency(Duration::from_millis(50)));
+        let start = std::time::Instant::now();
+        request.send_to(&api).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(70));
+    }
+}
@@ -0,0 +1,400 @@
+//! HTTP transports used to send requests to Giphy API
+//!
+//! `SyncApi` and `AsyncApi` build transport-agnostic [`HttpRequest`] objects and hand them
+//! over to a [`Transport`] or an [`AsyncTransport`] respectively. Implementations are
+//! provided for:
+//! - [`reqwest::blocking::Client`] and [`reqwest::Client`], with `reqwest` feature (default)
+//! - [`ureq::Agent`], with `ureq` feature
+//!
+//! Other HTTP libraries can be used by implementing the traits.
+//!
+//! [`HttpRequest`]: struct.HttpRequest.html
+//! [`Transport`]: trait.Transport.html
+//! [`AsyncTransport`]: trait.AsyncTransport.html
+//! [`reqwest::blocking::Client`]: https://docs.rs/reqwest/0.11/reqwest/blocking/struct.Client.html
+//! [`reqwest::Client`]: https://docs.rs/reqwest/0.11/reqwest/struct.Client.html
+//! [`ureq::Agent`]: https://docs.rs/ureq/3/ureq/struct.Agent.html
+
+use serde::Serialize;
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "async")]
+use futures::future::BoxFuture;
+
+use crate::error::GiphyError;
+
+/// Error returned by a transport when a request could not be sent or the response
+/// could not be read
+pub type TransportError = Box<dyn Error + Send + Sync>;
+
+/// HTTP method of a request to Giphy API
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Method {
+    #[default]
+    Get,
+    Post,
+}
+
+impl Method {
+    /// Returns the method name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+        }
+    }
+}
+
+/// Body of a request to Giphy API
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestBody {
+    /// Value of the `Content-Type` header
+    pub content_type: String,
+
+    pub bytes: Vec<u8>,
+}
+
+impl fmt::Debug for RequestBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestBody")
+            .field("content_type", &self.content_type)
+            .field("len", &self.bytes.len())
+            .finish()
+    }
+}
+
+/// HTTP request to Giphy API
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpRequest {
+    /// HTTP method, `GET` for all requests except uploads
+    #[serde(default)]
+    pub method: Method,
+
+    /// Endpoint URL, without the query string
+    pub url: String,
+
+    /// Query parameters, including the API key
+    pub query: Vec<(String, String)>,
+
+    /// Request body, only sent with `POST` requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<RequestBody>,
+}
+
+impl HttpRequest {
+    /// Builds a request to given endpoint, with the query serialized from `params`
+    pub(crate) fn new<P: Serialize>(
+        api_root: &str,
+        api_key: &str,
+        endpoint: &str,
+        params: &P,
+    ) -> Result<HttpRequest, GiphyError> {
+        let invalid = |e: &dyn Error| GiphyError::InvalidRequest {
+            reason: e.to_string(),
+            status: None,
+            meta: None,
+        };
+        let encoded = serde_urlencoded::to_string(params).map_err(|e| invalid(&e))?;
+        let params: Vec<(String, String)> =
+            serde_urlencoded::from_str(&encoded).map_err(|e| invalid(&e))?;
+
+        let mut query = vec![("api_key".to_string(), api_key.to_string())];
+        query.extend(params);
+
+        Ok(HttpRequest {
+            method: Method::Get,
+            url: format!("{}/{}", api_root, endpoint),
+            query,
+            body: None,
+        })
+    }
+
+    /// Removes query parameters with given names
+    #[cfg(any(feature = "sync", feature = "async"))]
+    pub(crate) fn without_params(mut self, names: &[&str]) -> Self {
+        self.query
+            .retain(|(name, _)| !names.contains(&name.as_str()));
+        self
+    }
+
+    /// Changes the method of the request and attaches given body
+    #[cfg(any(feature = "sync", feature = "async"))]
+    pub(crate) fn with_body(mut self, method: Method, body: Option<RequestBody>) -> Self {
+        self.method = method;
+        self.body = body;
+        self
+    }
+
+    /// Adds the `random_id` query parameter, unless the request already carries one
+    #[cfg(any(feature = "sync", feature = "async"))]
+    pub(crate) fn with_random_id(mut self, random_id: Option<&str>) -> Self {
+        if let Some(random_id) = random_id {
+            if !self.query.iter().any(|(name, _)| name == "random_id") {
+                self.query
+                    .push(("random_id".to_string(), random_id.to_string()));
+            }
+        }
+        self
+    }
+
+    /// Returns the request URL including the URL-encoded query string
+    pub fn url_with_query(&self) -> String {
+        if self.query.is_empty() {
+            return self.url.clone();
+        }
+
+        let query = serde_urlencoded::to_string(&self.query).unwrap_or_default();
+        format!("{}?{}", self.url, query)
+    }
+}
+
+/// HTTP response received from Giphy API
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpResponse {
+    pub status: u16,
+
+    /// Response headers, with lowercase names
+    pub headers: Vec<(String, String)>,
+
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// Returns the value of the first header with given name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Synchronous HTTP transport used by `SyncApi`
+#[cfg(feature = "sync")]
+pub trait Transport {
+    /// Sends the request, blocking the current thread until the response is read
+    ///
+    /// Responses with non-success statuses should be returned as `Ok`.
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, TransportError>;
+}
+
+#[cfg(feature = "sync")]
+impl<T: Transport + ?Sized> Transport for &T {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, TransportError> {
+        (**self).send(request)
+    }
+}
+
+/// Asynchronous HTTP transport used by `AsyncApi`
+#[cfg(feature = "async")]
+pub trait AsyncTransport {
+    /// Sends the request, returning a future resolving to the response
+    ///
+    /// Responses with non-success statuses should be returned as `Ok`.
+    fn send(
+        &self,
+        request: &HttpRequest,
+    ) -> BoxFuture<'static, Result<HttpResponse, TransportError>>;
+}
+
+#[cfg(feature = "reqwest")]
+fn reqwest_headers(headers: &reqwest::header::HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            Some((name.as_str().to_string(), value.to_str().ok()?.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(all(feature = "sync", feature = "reqwest"))]
+impl Transport for reqwest::blocking::Client {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, TransportError> {
+        let mut builder = match request.method {
+            Method::Get => self.get(&request.url),
+            Method::Post => self.post(&request.url),
+        }
+        .query(&request.query);
+        if let Some(body) = &request.body {
+            builder = builder
+                .header(reqwest::header::CONTENT_TYPE, &body.content_type)
+                .body(body.bytes.clone());
+        }
+        let response = builder.send()?;
+
+        let status = response.status().as_u16();
+        let headers = reqwest_headers(response.headers());
+        let body = response.text()?;
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+#[cfg(all(feature = "async", feature = "reqwest"))]
+impl AsyncTransport for reqwest::Client {
+    fn send(
+        &self,
+        request: &HttpRequest,
+    ) -> BoxFuture<'static, Result<HttpResponse, TransportError>> {
+        let mut builder = match request.method {
+            Method::Get => self.get(&request.url),
+            Method::Post => self.post(&request.url),
+        }
+        .query(&request.query);
+        if let Some(body) = &request.body {
+            builder = builder
+                .header(reqwest::header::CONTENT_TYPE, &body.content_type)
+                .body(body.bytes.clone());
+        }
+
+        Box::pin(async move {
+            let response = builder.send().await?;
+
+            let status = response.status().as_u16();
+            let headers = reqwest_headers(response.headers());
+            let body = response.text().await?;
+
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+#[cfg(all(feature = "sync", feature = "ureq"))]
+impl Transport for ureq::Agent {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, TransportError> {
+        let query = request.query.iter().map(|(k, v)| (k.as_str(), v.as_str()));
+        let mut response = match request.method {
+            Method::Get => self
+                .get(&request.url)
+                .query_pairs(query)
+                .config()
+                .http_status_as_error(false)
+                .build()
+                .call()?,
+            Method::Post => {
+                let builder = self
+                    .post(&request.url)
+                    .query_pairs(query)
+                    .config()
+                    .http_status_as_error(false)
+                    .build();
+                match &request.body {
+                    Some(body) => builder
+                        .header("Content-Type", &body.content_type)
+                        .send(&body.bytes[..])?,
+                    None => builder.send_empty()?,
+                }
+            }
+        };
+
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((name.as_str().to_string(), value.to_str().ok()?.to_string()))
+            })
+            .collect();
+        let body = response.body_mut().read_to_string()?;
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::v1::gifs::SearchRequest;
+
+    #[test]
+    fn build_request() {
+        let params = SearchRequest::new("funny cats").with_limit(10);
+        let request =
+            HttpRequest::new("https://api.giphy.com", "key", "v1/gifs/search", &params).unwrap();
+
+        assert_eq!(request.method, Method::Get);
+        assert_eq!(request.body, None);
+        assert_eq!(request.url, "https://api.giphy.com/v1/gifs/search");
+        assert_eq!(
+            request.query,
+            vec![
+                ("api_key".to_string(), "key".to_string()),
+                ("q".to_string(), "funny cats".to_string()),
+                ("limit".to_string(), "10".to_string()),
+            ]
+        );
+        assert_eq!(
+            request.url_with_query(),
+            "https://api.giphy.com/v1/gifs/search?api_key=key&q=funny+cats&limit=10"
+        );
+    }
+
+    #[test]
+    fn response_header_lookup() {
+        let response = HttpResponse {
+            status: 429,
+            headers: vec![("retry-after".to_string(), "30".to_string())],
+            body: String::new(),
+        };
+
+        assert_eq!(response.header("Retry-After"), Some("30"));
+        assert_eq!(response.header("content-type"), None);
+    }
+
+    #[cfg(all(feature = "sync", feature = "ureq"))]
+    #[test]
+    fn ureq_transport() {
+        use crate::v1::gifs::GetGifRequest;
+        use crate::v1::sync::{RunnableSyncRequest, SyncApi};
+        use crate::v1::upload::UploadRequest;
+        use mockito::{mock, server_url};
+
+        let _ok = mock("GET", "/v1/gifs/xT4uQulxzV39haRFjG?api_key=test-api-key")
+            .with_status(200)
+            .with_body_from_file("data/example-get-gif-response.json")
+            .create();
+        let _not_found = mock("GET", "/v1/gifs/missing?api_key=test-api-key")
+            .with_status(404)
+            .with_body(r#"{"data":[],"meta":{"msg":"Not Found","status":404,"response_id":"abc"}}"#)
+            .create();
+
+        let agent = ureq::Agent::new_with_defaults();
+        let api = SyncApi::new_with_url(server_url(), "test-api-key".to_string(), agent);
+
+        let response = GetGifRequest::new("xT4uQulxzV39haRFjG")
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while calling get gif endpoint: {:?}", e));
+        assert_eq!(response.data.id, "xT4uQulxzV39haRFjG");
+
+        let err = GetGifRequest::new("missing").send_to(&api).unwrap_err();
+        assert_eq!(err.status(), Some(404));
+
+        let _upload = mock("POST", "/v1/gifs?api_key=test-api-key")
+            .match_header(
+                "content-type",
+                mockito::Matcher::Regex("^multipart/form-data".to_string()),
+            )
+            .match_body(mockito::Matcher::Regex("GIF89a".to_string()))
+            .with_status(200)
+            .with_body_from_file("data/example-upload-response.json")
+            .create();
+
+        let response = UploadRequest::from_bytes("tacos.gif", &b"GIF89a"[..])
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while calling upload endpoint: {:?}", e));
+        assert_eq!(response.data.id, "3o7TKMt1VVNkHV2PaE");
+    }
+}
@@ -0,0 +1,560 @@
+//! Analytics pingbacks reporting user actions on GIFs
+//!
+//! Giphy asks API partners to report when GIFs are shown, clicked and sent, using the
+//! pingback URLs returned in the [`Gif`] `analytics` field. Reporters created using
+//! `SyncApi::analytics_reporter` or `AsyncApi::analytics_reporter` buffer the pingbacks
+//! and send them in batches, tagged with the `random_id` identifying the user. Pingbacks
+//! that fail to send are kept and retried with the next batch, and pending pingbacks are
+//! sent when the reporter is dropped.
+//!
+//! ```no_run
+//! use giphy::v1::analytics::Action;
+//! use giphy::v1::gifs::SearchRequest;
+//! use giphy::v1::sync::*;
+//!
+//! # let client = reqwest::blocking::Client::new();
+//! # let api = SyncApi::new("[your Giphy API key]".to_string(), client);
+//! let mut reporter = api.analytics_reporter("e0771ad3f2b64f2d");
+//! let response = SearchRequest::new("tacos").send_to(&api).unwrap();
+//! for gif in &response.data {
+//!     reporter.record(gif, Action::Seen).unwrap();
+//! }
+//! ```
+//!
+//! [`Gif`]: ../struct.Gif.html
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "async")]
+use std::sync::Arc;
+
+use super::model::Gif;
+#[cfg(any(feature = "sync", feature = "async"))]
+use crate::error::check_status;
+use crate::error::GiphyError;
+#[cfg(feature = "async")]
+use crate::transport::AsyncTransport;
+#[cfg(feature = "sync")]
+use crate::transport::Transport;
+use crate::transport::{HttpRequest, Method};
+
+/// Number of pingbacks buffered before they are sent, unless changed using `with_batch_size`
+pub const DEFAULT_BATCH_SIZE: usize = 20;
+
+/// Maximum number of pingbacks buffered by a reporter, older pingbacks are dropped
+pub const MAX_PENDING: usize = 1000;
+
+/// User action reported by an analytics pingback
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// The GIF was shown to the user, reported using the `onload` URL
+    Seen,
+    /// The user clicked the GIF, reported using the `onclick` URL
+    Click,
+    /// The user sent or shared the GIF, reported using the `onsent` URL
+    Sent,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Action::Seen => "onload",
+            Action::Click => "onclick",
+            Action::Sent => "onsent",
+        })
+    }
+}
+
+impl Gif {
+    /// Returns the analytics pingback URL reporting given action, if Giphy returned one
+    pub fn pingback_url(&self, action: Action) -> Option<&str> {
+        let analytics = self.analytics.as_ref()?;
+        let pingback = match action {
+            Action::Seen => &analytics.onload,
+            Action::Click => &analytics.onclick,
+            Action::Sent => &analytics.onsent,
+        };
+        pingback.as_ref().map(|pingback| pingback.url.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Pingback {
+    url: String,
+    /// Time of the action, in milliseconds since Unix epoch
+    ts: u64,
+}
+
+/// Pingbacks buffered by the synchronous and asynchronous reporters
+struct PingbackQueue {
+    random_id: String,
+    batch_size: usize,
+    pending: VecDeque<Pingback>,
+}
+
+impl PingbackQueue {
+    fn new(random_id: String) -> PingbackQueue {
+        PingbackQueue {
+            random_id,
+            batch_size: DEFAULT_BATCH_SIZE,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Buffers a pingback, returning `true` if a full batch is ready to be sent
+    fn push(&mut self, gif: &Gif, action: Action) -> Result<bool, GiphyError> {
+        let url = gif
+            .pingback_url(action)
+            .ok_or_else(|| GiphyError::InvalidRequest {
+                reason: format!("GIF {} has no {} pingback URL", gif.id, action),
+                status: None,
+                meta: None,
+            })?;
+
+        if self.pending.len() >= MAX_PENDING {
+            self.pending.pop_front();
+        }
+        self.pending.push_back(Pingback {
+            url: url.to_string(),
+            ts: unix_millis(),
+        });
+        Ok(self.pending.len() >= self.batch_size)
+    }
+
+    fn take(&mut self) -> Vec<Pingback> {
+        self.pending.drain(..).collect()
+    }
+
+    /// Puts back pingbacks that failed to send, ahead of the ones buffered since
+    fn requeue(&mut self, failed: Vec<Pingback>) {
+        for pingback in failed.into_iter().rev() {
+            self.pending.push_front(pingback);
+        }
+        while self.pending.len() > MAX_PENDING {
+            self.pending.pop_front();
+        }
+    }
+
+    fn request(&self, pingback: &Pingback) -> HttpRequest {
+        let (url, query) = pingback
+            .url
+            .split_once('?')
+            .unwrap_or((pingback.url.as_str(), ""));
+        let mut query: Vec<(String, String)> =
+            serde_urlencoded::from_str(query).unwrap_or_default();
+        query.push(("random_id".to_string(), self.random_id.clone()));
+        query.push(("ts".to_string(), pingback.ts.to_string()));
+
+        HttpRequest {
+            method: Method::Get,
+            url: url.to_string(),
+            query,
+            body: None,
+        }
+    }
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Reporter sending analytics pingbacks using a synchronous [`Transport`]
+///
+/// Created using `SyncApi::analytics_reporter`.
+///
+/// [`Transport`]: ../../transport/trait.Transport.html
+#[cfg(feature = "sync")]
+pub struct AnalyticsReporter<'a, T: Transport> {
+    transport: &'a T,
+    queue: PingbackQueue,
+}
+
+#[cfg(feature = "sync")]
+impl<'a, T: Transport> AnalyticsReporter<'a, T> {
+    pub(crate) fn new(transport: &'a T, random_id: String) -> AnalyticsReporter<'a, T> {
+        AnalyticsReporter {
+            transport,
+            queue: PingbackQueue::new(random_id),
+        }
+    }
+
+    /// Sends pingbacks in batches of given size, `1` sends every pingback immediately
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.queue.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Buffers a pingback reporting the action on the GIF, sending the batch once it is full
+    ///
+    /// Fails if the GIF has no pingback URL for the action, or if sending the batch fails.
+    pub fn record(&mut self, gif: &Gif, action: Action) -> Result<(), GiphyError> {
+        if self.queue.push(gif, action)? {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of pingbacks waiting to be sent
+    pub fn pending(&self) -> usize {
+        self.queue.pending.len()
+    }
+
+    /// Sends all pending pingbacks, stopping at the first failure
+    ///
+    /// Pingbacks that were not sent are kept and sent with the next batch.
+    pub fn flush(&mut self) -> Result<(), GiphyError> {
+        let mut pingbacks = self.queue.take().into_iter();
+        while let Some(pingback) = pingbacks.next() {
+            let result = self
+                .transport
+                .send(&self.queue.request(&pingback))
+                .map_err(GiphyError::Transport)
+                .and_then(check_status);
+            if let Err(e) = result {
+                self.queue
+                    .requeue(std::iter::once(pingback).chain(pingbacks).collect());
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sync")]
+impl<'a, T: Transport> Drop for AnalyticsReporter<'a, T> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Reporter sending analytics pingbacks using an [`AsyncTransport`]
+///
+/// Created using `AsyncApi::analytics_reporter`.
+///
+/// Sending pingbacks pending when the reporter is dropped is best effort. They are sent by
+/// a task spawned on the current Tokio runtime, which stops at the first failed pingback
+/// without reporting the failure. If the reporter is dropped outside of a Tokio runtime,
+/// pending pingbacks are discarded. Call [`flush`] before dropping the reporter to handle
+/// failures.
+///
+/// [`flush`]: #method.flush
+/// [`AsyncTransport`]: ../../transport/trait.AsyncTransport.html
+#[cfg(feature = "async")]
+pub struct AsyncAnalyticsReporter<T: AsyncTransport + Send + Sync + 'static> {
+    transport: Arc<T>,
+    queue: PingbackQueue,
+}
+
+#[cfg(feature = "async")]
+impl<T: AsyncTransport + Send + Sync + 'static> AsyncAnalyticsReporter<T> {
+    pub(crate) fn new(transport: Arc<T>, random_id: String) -> AsyncAnalyticsReporter<T> {
+        AsyncAnalyticsReporter {
+            transport,
+            queue: PingbackQueue::new(random_id),
+        }
+    }
+
+    /// Sends pingbacks in batches of given size, `1` sends every pingback immediately
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.queue.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Buffers a pingback reporting the action on the GIF, sending the batch once it is full
+    ///
+    /// Fails if the GIF has no pingback URL for the action, or if sending the batch fails.
+    pub async fn record(&mut self, gif: &Gif, action: Action) -> Result<(), GiphyError> {
+        if self.queue.push(gif, action)? {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of pingbacks waiting to be sent
+    pub fn pending(&self) -> usize {
+        self.queue.pending.len()
+    }
+
+    /// Sends all pending pingbacks, stopping at the first failure
+    ///
+    /// Pingbacks that were not sent are kept and sent with the next batch.
+    pub async fn flush(&mut self) -> Result<(), GiphyError> {
+        let mut pingbacks = self.queue.take().into_iter();
+        while let Some(pingback) = pingbacks.next() {
+            let request = self.queue.request(&pingback);
+            let result = self
+                .transport
+                .send(&request)
+                .await
+                .map_err(GiphyError::Transport)
+                .and_then(check_status);
+            if let Err(e) = result {
+                self.queue
+                    .requeue(std::iter::once(pingback).chain(pingbacks).collect());
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T: AsyncTransport + Send + Sync + 'static> Drop for AsyncAnalyticsReporter<T> {
+    fn drop(&mut self) {
+        let requests: Vec<HttpRequest> = self
+            .queue
+            .take()
+            .iter()
+            .map(|pingback| self.queue.request(pingback))
+            .collect();
+        if requests.is_empty() {
+            return;
+        }
+
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let transport = self.transport.clone();
+            runtime.spawn(async move {
+                for request in requests {
+                    let result = transport
+                        .send(&request)
+                        .await
+                        .map_err(GiphyError::Transport)
+                        .and_then(check_status);
+                    if result.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::v1::PaginatedGifListResponse;
+
+    pub(crate) fn gifs() -> Vec<Gif> {
+        let response: PaginatedGifListResponse =
+            serde_json::from_str(include_str!("../../data/example-search-response.json")).unwrap();
+        response.data
+    }
+
+    /// Returns the example GIFs with pingback URLs pointing at given mock server path
+    #[cfg(feature = "reqwest")]
+    fn local_gifs(path: &str) -> Vec<Gif> {
+        let mut gifs = gifs();
+        for analytics in gifs.iter_mut().filter_map(|gif| gif.analytics.as_mut()) {
+            let pingbacks = vec![
+                &mut analytics.onload,
+                &mut analytics.onclick,
+                &mut analytics.onsent,
+            ];
+            for pingback in pingbacks.into_iter().flatten() {
+                let query = pingback.url.split_once('?').unwrap().1.to_string();
+                pingback.url = format!("{}{}?{}", mockito::server_url(), path, query);
+            }
+        }
+        gifs
+    }
+
+    #[test]
+    fn gif_analytics() {
+        let gifs = gifs();
+        let gif = &gifs[0];
+
+        assert!(gif.analytics_response_payload.is_some());
+        assert!(gif
+            .pingback_url(Action::Seen)
+            .unwrap()
+            .ends_with("action_type=SEEN"));
+        assert!(gif
+            .pingback_url(Action::Sent)
+            .unwrap()
+            .ends_with("action_type=SENT"));
+    }
+
+    #[test]
+    fn pingback_request() {
+        let gifs = gifs();
+        let mut queue = PingbackQueue::new("user-1".to_string());
+        assert!(!queue.push(&gifs[0], Action::Click).unwrap());
+
+        let pingback = queue.take().pop().unwrap();
+        let request = queue.request(&pingback);
+        assert_eq!(
+            request.url,
+            "https://giphy_analytics.giphy.com/simple_analytics"
+        );
+        assert_eq!(
+            request.query[..5],
+            [
+                (
+                    "response_id".to_string(),
+                    "5c72dcea6648432f411511a9".to_string()
+                ),
+                ("event_type".to_string(), "GIF_SEARCH".to_string()),
+                ("gif_id".to_string(), "22CEvbj04nLLq".to_string()),
+                ("action_type".to_string(), "CLICK".to_string()),
+                ("random_id".to_string(), "user-1".to_string()),
+            ]
+        );
+        assert_eq!(
+            request.query[5],
+            ("ts".to_string(), pingback.ts.to_string())
+        );
+    }
+
+    #[test]
+    fn queue_batches_and_requeues() {
+        let gifs = gifs();
+        let mut queue = PingbackQueue::new("user-1".to_string());
+        queue.batch_size = 2;
+
+        assert!(!queue.push(&gifs[0], Action::Seen).unwrap());
+        assert!(queue.push(&gifs[1], Action::Seen).unwrap());
+
+        let failed = queue.take();
+        queue.push(&gifs[2], Action::Seen).unwrap();
+        queue.requeue(failed);
+
+        let urls: Vec<_> = queue.pending.iter().map(|p| p.url.clone()).collect();
+        assert!(urls[0].contains(&gifs[0].id));
+        assert!(urls[1].contains(&gifs[1].id));
+        assert!(urls[2].contains(&gifs[2].id));
+    }
+
+    #[test]
+    fn missing_pingback_url() {
+        let mut gif = gifs().remove(0);
+        gif.analytics = None;
+
+        let mut queue = PingbackQueue::new("user-1".to_string());
+        let err = queue.push(&gif, Action::Seen).unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::InvalidRequest);
+        assert!(queue.pending.is_empty());
+    }
+
+    #[cfg(all(feature = "sync", feature = "reqwest"))]
+    mod sync {
+        use super::*;
+        use crate::v1::sync::SyncApi;
+        use mockito::{mock, server_url, Matcher};
+
+        #[test]
+        fn reporter_batches_and_flushes_on_drop() {
+            let pingbacks = mock("GET", "/pingback/sync")
+                .match_query(Matcher::AllOf(vec![
+                    Matcher::UrlEncoded("random_id".to_string(), "user-1".to_string()),
+                    Matcher::UrlEncoded("action_type".to_string(), "SEEN".to_string()),
+                    Matcher::Regex("ts=[0-9]+".to_string()),
+                ]))
+                .with_status(200)
+                .expect(3)
+                .create();
+
+            let client = reqwest::blocking::Client::new();
+            let api = SyncApi::new_with_url(server_url(), "key".to_string(), client);
+            let gifs = local_gifs("/pingback/sync");
+
+            let mut reporter = api.analytics_reporter("user-1").with_batch_size(2);
+            reporter.record(&gifs[0], Action::Seen).unwrap();
+            assert_eq!(reporter.pending(), 1);
+            reporter.record(&gifs[1], Action::Seen).unwrap();
+            assert_eq!(reporter.pending(), 0);
+            reporter.record(&gifs[2], Action::Seen).unwrap();
+            drop(reporter);
+
+            pingbacks.assert();
+        }
+
+        #[test]
+        fn reporter_keeps_failed_pingbacks() {
+            let _failing = mock("GET", "/pingback/failing")
+                .match_query(Matcher::Any)
+                .with_status(503)
+                .create();
+
+            let client = reqwest::blocking::Client::new();
+            let api = SyncApi::new_with_url(server_url(), "key".to_string(), client);
+            let gifs = local_gifs("/pingback/failing");
+
+            let mut reporter = api.analytics_reporter("user-1").with_batch_size(2);
+            reporter.record(&gifs[0], Action::Click).unwrap();
+            let err = reporter.record(&gifs[1], Action::Click).unwrap_err();
+            assert_eq!(err.status(), Some(503));
+            assert_eq!(reporter.pending(), 2);
+            assert!(reporter.flush().is_err());
+            assert_eq!(reporter.pending(), 2);
+        }
+    }
+
+    #[cfg(all(feature = "async", feature = "reqwest"))]
+    mod r#async {
+        use super::*;
+        use crate::v1::r#async::AsyncApi;
+        use mockito::{mock, server_url, Matcher};
+        use std::time::Duration;
+
+        #[tokio::test]
+        async fn reporter_sends_batches() {
+            let pingbacks = mock("GET", "/pingback/async")
+                .match_query(Matcher::UrlEncoded(
+                    "random_id".to_string(),
+                    "user-2".to_string(),
+                ))
+                .with_status(200)
+                .expect(2)
+                .create();
+
+            let client = reqwest::Client::new();
+            let api = AsyncApi::new_with_url(server_url(), "key".to_string(), client);
+            let gifs = local_gifs("/pingback/async");
+
+            let mut reporter = api.analytics_reporter("user-2").with_batch_size(3);
+            reporter.record(&gifs[0], Action::Sent).await.unwrap();
+            reporter.record(&gifs[1], Action::Sent).await.unwrap();
+            assert_eq!(reporter.pending(), 2);
+            reporter.flush().await.unwrap();
+            assert_eq!(reporter.pending(), 0);
+
+            pingbacks.assert();
+        }
+
+        #[tokio::test]
+        async fn reporter_flushes_on_drop() {
+            let pingbacks = mock("GET", "/pingback/async-drop")
+                .match_query(Matcher::AllOf(vec![
+                    Matcher::UrlEncoded("random_id".to_string(), "user-3".to_string()),
+                    Matcher::UrlEncoded("action_type".to_string(), "CLICK".to_string()),
+                ]))
+                .with_status(200)
+                .expect(2)
+                .create();
+
+            let client = reqwest::Client::new();
+            let api = AsyncApi::new_with_url(server_url(), "key".to_string(), client);
+            let gifs = local_gifs("/pingback/async-drop");
+
+            let mut reporter = api.analytics_reporter("user-3");
+            reporter.record(&gifs[0], Action::Click).await.unwrap();
+            reporter.record(&gifs[1], Action::Click).await.unwrap();
+            assert_eq!(reporter.pending(), 2);
+            drop(reporter);
+
+            // Pingbacks are sent by a spawned task, give it time to run
+            for _ in 0..100 {
+                if pingbacks.matched() {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            pingbacks.assert();
+        }
+    }
+}
@@ -1,74 +1,403 @@
-use super::model::{GiphyRequest, API_ROOT};
+use super::analytics::AsyncAnalyticsReporter;
+use super::categories::{
+    next_offset, CategoriesRequest, CategoryListRequest, SubcategoriesRequest, MAX_DEPTH,
+};
+use super::channels::ChannelGifsRequest;
+use super::model::{
+    ApiHost, Category, CategoryListResponse, Gif, GiphyRequest, PaginatedGifListResponse, API_ROOT,
+    UPLOAD_ROOT,
+};
+use super::pagination::{PaginatedRequest, Paginator};
+use super::params::RandomId;
+use crate::cache::ResponseCache;
+use crate::error::{decode_response, handle_response, GiphyError};
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
+use crate::transport::{AsyncTransport, HttpRequest, Method};
 use futures::future::BoxFuture;
-use futures::TryFutureExt;
+use futures::task::{Context, Poll};
+use futures::{FutureExt, Stream, TryStreamExt};
 use serde::de::DeserializeOwned;
 use std::marker::Send;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
 
-/// Implementation of Giphy API that uses asynchronous [`reqwest::async::Client`]
+/// Implementation of Giphy API that uses an [`AsyncTransport`]
 ///
-/// [`reqwest::async::Client`]: https://docs.rs/reqwest/0.11.4/reqwest/struct.Client.html
-pub struct AsyncApi {
+/// With the default `reqwest` feature, the transport is [`reqwest::Client`].
+///
+/// [`AsyncTransport`]: ../../transport/trait.AsyncTransport.html
+/// [`reqwest::Client`]: https://docs.rs/reqwest/0.11.4/reqwest/struct.Client.html
+pub struct AsyncApi<
+    #[cfg(feature = "reqwest")] T = reqwest::Client,
+    #[cfg(not(feature = "reqwest"))] T,
+> {
     url: String,
+    upload_url: String,
     key: String,
-    client: reqwest::Client,
+    transport: Arc<T>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
+    random_id: Option<RandomId>,
+    sleep: Sleep,
 }
 
-impl AsyncApi {
+/// Function returning a future that completes after given delay
+pub(crate) type Sleep = Arc<dyn Fn(Duration) -> BoxFuture<'static, ()> + Send + Sync>;
+
+impl<T> AsyncApi<T>
+where
+    T: AsyncTransport + Send + Sync + 'static,
+{
     /// Creates a new Giphy API Client
-    pub fn new(key: String, client: reqwest::Client) -> AsyncApi {
-        AsyncApi {
-            url: API_ROOT.to_string(),
-            key,
-            client,
-        }
+    pub fn new(key: String, transport: T) -> AsyncApi<T> {
+        AsyncApi::new_with_url(API_ROOT.to_string(), key, transport)
+            .with_upload_url(UPLOAD_ROOT.to_string())
     }
 
     /// Creates a new Giphy API Client with a custom API root
     ///
-    /// Useful for testing against API mocks
-    pub fn new_with_url(api_root_url: String, key: String, client: reqwest::Client) -> AsyncApi {
+    /// Useful for testing against API mocks. Uploads are sent to the same API root, unless
+    /// changed using `with_upload_url`.
+    pub fn new_with_url(api_root_url: String, key: String, transport: T) -> AsyncApi<T> {
         AsyncApi {
+            upload_url: api_root_url.clone(),
             url: api_root_url,
             key,
-            client,
+            transport: Arc::new(transport),
+            retry_policy: None,
+            rate_limiter: None,
+            cache: None,
+            random_id: None,
+            sleep: Arc::new(|delay| tokio::time::sleep(delay).boxed()),
         }
     }
+
+    /// Sends uploads to a custom upload API root
+    pub fn with_upload_url(mut self, upload_url: String) -> Self {
+        self.upload_url = upload_url;
+        self
+    }
+
+    /// Retries failed `GET` requests according to given [`RetryPolicy`]
+    ///
+    /// Uploads are never retried, since a failed response does not mean the upload failed.
+    /// Delays between attempts use `tokio::time::sleep`, which needs a Tokio runtime with the
+    /// time driver enabled, unless changed using `with_sleep`.
+    ///
+    /// [`RetryPolicy`]: ../../struct.RetryPolicy.html
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Limits the rate of requests made to Giphy API using given [`RateLimiter`]
+    ///
+    /// In [`RateLimitMode::Wait`] mode the limiter waits using `tokio::time::sleep`, which
+    /// needs a Tokio runtime with the time driver enabled, unless changed using `with_sleep`.
+    ///
+    /// [`RateLimiter`]: ../../struct.RateLimiter.html
+    /// [`RateLimitMode::Wait`]: ../../enum.RateLimitMode.html#variant.Wait
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Waits between retries and for the rate limiter using given function
+    ///
+    /// Allows retries and rate limiting outside of a Tokio runtime, e.g. with `async-std`
+    /// using `|delay| async_std::task::sleep(delay).boxed()`.
+    pub fn with_sleep<F>(mut self, sleep: F) -> Self
+    where
+        F: Fn(Duration) -> BoxFuture<'static, ()> + Send + Sync + 'static,
+    {
+        self.sleep = Arc::new(sleep);
+        self
+    }
+
+    /// Caches responses using given [`ResponseCache`]
+    ///
+    /// [`ResponseCache`]: ../../cache/struct.ResponseCache.html
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Returns the [`RateLimiter`] attached to the client, if there is one
+    ///
+    /// [`RateLimiter`]: ../../struct.RateLimiter.html
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Creates a session sharing the client, which sends every request with given `random_id`
+    ///
+    /// The session shares the transport, retry policy, rate limiter and cache of the client.
+    /// Requests that set their own `random_id` keep it.
+    pub fn session(&self, random_id: impl Into<RandomId>) -> AsyncApi<T> {
+        AsyncApi {
+            url: self.url.clone(),
+            upload_url: self.upload_url.clone(),
+            key: self.key.clone(),
+            transport: self.transport.clone(),
+            retry_policy: self.retry_policy.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+            cache: self.cache.clone(),
+            random_id: Some(random_id.into()),
+            sleep: self.sleep.clone(),
+        }
+    }
+
+    /// Returns the `random_id` sent with every request, if the client is a session
+    pub fn random_id(&self) -> Option<&RandomId> {
+        self.random_id.as_ref()
+    }
+
+    /// Creates an [`AsyncAnalyticsReporter`] sending analytics pingbacks for the user with given
+    /// `random_id`
+    ///
+    /// [`AsyncAnalyticsReporter`]: ../analytics/struct.AsyncAnalyticsReporter.html
+    pub fn analytics_reporter(&self, random_id: impl Into<String>) -> AsyncAnalyticsReporter<T> {
+        AsyncAnalyticsReporter::new(self.transport.clone(), random_id.into())
+    }
+
+    /// Walks all pages of a [`PaginatedRequest`], yielding GIFs one by one
+    ///
+    /// Pages are fetched lazily starting from the request offset, until all results are
+    /// returned or Giphy [`MAX_OFFSET`] is reached. GIFs already returned on previous
+    /// pages are skipped.
+    ///
+    /// [`PaginatedRequest`]: ../pagination/trait.PaginatedRequest.html
+    /// [`MAX_OFFSET`]: ../pagination/constant.MAX_OFFSET.html
+    pub fn paginate<R: PaginatedRequest>(&self, request: R) -> PaginatedStream<'_, R, T> {
+        PaginatedStream {
+            api: self,
+            pages: Paginator::new(request),
+            in_flight: None,
+        }
+    }
+
+    /// Fetches all GIFs of the channel with given id, walking all pages of a
+    /// [`ChannelGifsRequest`]
+    ///
+    /// [`ChannelGifsRequest`]: ../channels/struct.ChannelGifsRequest.html
+    pub async fn channel_gifs(&self, channel_id: u64) -> Result<Vec<Gif>, GiphyError> {
+        self.paginate(ChannelGifsRequest::new(channel_id))
+            .try_collect()
+            .await
+    }
+
+    /// Fetches Giphy categories, walking the category tree to given depth
+    ///
+    /// Depth `1` returns top-level categories, with subcategories listed by name only.
+    /// Depth `2` also fetches subcategories of each category, including their featured GIFs.
+    /// Depths greater than [`MAX_DEPTH`] are the same as `MAX_DEPTH`. All pages of each
+    /// category list are fetched.
+    ///
+    /// [`MAX_DEPTH`]: ../categories/constant.MAX_DEPTH.html
+    pub async fn category_tree(&self, depth: usize) -> Result<Vec<Category>, GiphyError> {
+        if depth == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut categories = self.all_categories(CategoriesRequest::new()).await?;
+        if depth >= MAX_DEPTH {
+            for category in &mut categories {
+                let request = SubcategoriesRequest::new(&category.name_encoded);
+                category.subcategories = self.all_categories(request).await?;
+            }
+        }
+        Ok(categories)
+    }
+
+    async fn all_categories<R: CategoryListRequest>(
+        &self,
+        mut request: R,
+    ) -> Result<Vec<Category>, GiphyError> {
+        let mut categories = Vec::new();
+        loop {
+            let page: CategoryListResponse = request.send_to(self).await?;
+            let next = next_offset(&page);
+            categories.extend(page.data);
+            match next {
+                Some(offset) => request.set_offset(offset),
+                None => return Ok(categories),
+            }
+        }
+    }
+
+    fn send<ResponseType>(
+        &self,
+        request: HttpRequest,
+        ttl: Option<Duration>,
+    ) -> BoxFuture<'static, Result<ResponseType, GiphyError>>
+    where
+        ResponseType: DeserializeOwned + Send + 'static,
+    {
+        let transport = self.transport.clone();
+        let retry_policy = self.retry_policy.clone();
+        let rate_limiter = self.rate_limiter.clone();
+        let cache = self.cache.clone().zip(ttl);
+        let sleep = self.sleep.clone();
+
+        Box::pin(async move {
+            if let Some(body) = cache.as_ref().and_then(|(cache, _)| cache.get(&request)) {
+                return decode_response(body);
+            }
+
+            let mut attempt = 1;
+            loop {
+                if let Some(rate_limiter) = &rate_limiter {
+                    rate_limiter.acquire(&sleep).await?;
+                }
+
+                let result = transport
+                    .send(&request)
+                    .await
+                    .map_err(GiphyError::Transport)
+                    .and_then(|response| match &cache {
+                        Some((cache, ttl)) => cache.handle_response(&request, response, *ttl),
+                        None => handle_response(response),
+                    });
+
+                let delay = match (&result, &retry_policy) {
+                    (Err(e), Some(policy)) if request.method == Method::Get => {
+                        policy.retry_delay(attempt, e)
+                    }
+                    _ => None,
+                };
+                match delay {
+                    Some(delay) => sleep(delay).await,
+                    None => return result,
+                }
+                attempt += 1;
+            }
+        })
+    }
 }
 
 pub trait RunnableAsyncRequest<ResponseType> {
     /// Sends a request to an [AsyncApi]
     ///
     /// [SyncApi]: ./struct.AsyncApi.html
-    fn send_to(&self, api: &AsyncApi) -> BoxFuture<'static, Result<ResponseType, reqwest::Error>>;
+    fn send_to<T>(&self, api: &AsyncApi<T>) -> BoxFuture<'static, Result<ResponseType, GiphyError>>
+    where
+        T: AsyncTransport + Send + Sync + 'static;
 }
 
-impl<'a, RequestType, ResponseType> RunnableAsyncRequest<ResponseType> for RequestType
+impl<RequestType, ResponseType> RunnableAsyncRequest<ResponseType> for RequestType
 where
     RequestType: GiphyRequest<ResponseType>,
     ResponseType: DeserializeOwned + Send + 'static,
 {
-    fn send_to(&self, api: &AsyncApi) -> BoxFuture<'static, Result<ResponseType, reqwest::Error>> {
-        let endpoint = format!("{}/{}", api.url, self.get_endpoint());
+    fn send_to<T>(&self, api: &AsyncApi<T>) -> BoxFuture<'static, Result<ResponseType, GiphyError>>
+    where
+        T: AsyncTransport + Send + Sync + 'static,
+    {
+        let api_root = match self.host() {
+            ApiHost::Api => &api.url,
+            ApiHost::Upload => &api.upload_url,
+        };
+        match HttpRequest::new(api_root, &api.key, self.get_endpoint(), self) {
+            Ok(request) => {
+                let request = request
+                    .without_params(self.path_params())
+                    .with_body(self.method(), self.body())
+                    .with_random_id(api.random_id.as_ref().map(RandomId::as_str));
+                let ttl = api
+                    .cache
+                    .as_ref()
+                    .filter(|_| request.method == Method::Get)
+                    .and_then(|cache| cache.ttl_for(self.get_endpoint(), self.cache_ttl()));
+                api.send(request, ttl)
+            }
+            Err(e) => Box::pin(futures::future::ready(Err(e))),
+        }
+    }
+}
+
+/// Stream of GIFs from all pages of a [`PaginatedRequest`]
+///
+/// Created using [`AsyncApi::paginate`]. The stream ends after the first error.
+///
+/// [`PaginatedRequest`]: ../pagination/trait.PaginatedRequest.html
+/// [`AsyncApi::paginate`]: ./struct.AsyncApi.html#method.paginate
+pub struct PaginatedStream<'a, R, T> {
+    api: &'a AsyncApi<T>,
+    pages: Paginator<R>,
+    in_flight: Option<BoxFuture<'static, Result<PaginatedGifListResponse, GiphyError>>>,
+}
+
+impl<'a, R, T> PaginatedStream<'a, R, T>
+where
+    R: PaginatedRequest,
+    T: AsyncTransport + Send + Sync + 'static,
+{
+    /// Limits the total number of GIFs returned by the stream
+    pub fn with_max_items(mut self, max_items: usize) -> Self {
+        self.pages.set_max_items(max_items);
+        self
+    }
+}
+
+impl<'a, R, T> Stream for PaginatedStream<'a, R, T>
+where
+    R: PaginatedRequest + Unpin,
+    T: AsyncTransport + Send + Sync + 'static,
+{
+    type Item = Result<Gif, GiphyError>;
 
-        let future = api
-            .client
-            .get(&endpoint)
-            .query(&[("api_key", &api.key)])
-            .query(&self)
-            .send()
-            .and_then(|response| response.json::<ResponseType>());
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(in_flight) = this.in_flight.as_mut() {
+                let response = futures::ready!(in_flight.poll_unpin(cx));
+                this.in_flight = None;
+                match response {
+                    Ok(page) => this.pages.accept_page(page),
+                    Err(e) => {
+                        this.pages.finish();
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                }
+            }
 
-        Box::pin(future)
+            if let Some(gif) = this.pages.next_buffered() {
+                return Poll::Ready(Some(Ok(gif)));
+            }
+
+            match this.pages.next_request() {
+                Some(request) => this.in_flight = Some(request.send_to(this.api)),
+                None => return Poll::Ready(None),
+            }
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod test {
     use super::*;
     use crate::v1;
+    use crate::v2;
+    use crate::{RateLimiter, RetryPolicy};
     use dotenv::dotenv;
+    use futures::{StreamExt, TryStreamExt};
     use mockito::{mock, server_url, Matcher};
     use std::env;
+    use std::time::Duration;
+
+    fn search_page(offset: u32, first_id: u32, total_count: u32) -> String {
+        let mut page: serde_json::Value =
+            serde_json::from_str(include_str!("../../data/example-search-response.json")).unwrap();
+        for (i, gif) in page["data"].as_array_mut().unwrap().iter_mut().enumerate() {
+            gif["id"] = format!("gif{}", first_id + i as u32).into();
+        }
+        page["pagination"]["offset"] = offset.into();
+        page["pagination"]["total_count"] = total_count.into();
+        page.to_string()
+    }
 
     #[tokio::test]
     async fn api_search_200_ok() {
@@ -217,4 +546,562 @@ mod test {
 
         assert!(response.meta.status == 200);
     }
+
+    #[tokio::test]
+    async fn api_stickers_search_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/stickers/search.*api_key=.+q=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-stickers-search-response.json")
+        .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::stickers::SearchRequest::new("rage")
+            .send_to(&api)
+            .await
+            .unwrap();
+
+        assert!(response.pagination.count > 0);
+        assert!(response.data.iter().all(|s| s.gif_type == "sticker"));
+    }
+
+    #[tokio::test]
+    async fn api_stickers_trending_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/stickers/trending.*api_key=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-stickers-trending-response.json")
+        .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::stickers::TrendingRequest::new()
+            .send_to(&api)
+            .await
+            .unwrap();
+
+        assert!(response.pagination.count > 0);
+    }
+
+    #[tokio::test]
+    async fn api_stickers_translate_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/stickers/translate.*api_key=.+&s=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-stickers-translate-response.json")
+        .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::stickers::TranslateRequest::new("rage")
+            .send_to(&api)
+            .await
+            .unwrap();
+
+        assert!(response.meta.status == 200);
+    }
+
+    #[tokio::test]
+    async fn api_stickers_random_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/stickers/random.*api_key=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-stickers-random-response.json")
+        .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::stickers::RandomRequest::new()
+            .send_to(&api)
+            .await
+            .unwrap();
+
+        assert!(response.meta.status == 200);
+    }
+
+    #[tokio::test]
+    async fn api_related_tags_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/tags/related/rage.*api_key=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-related-tags-response.json")
+        .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::tags::RelatedTagsRequest::new("rage")
+            .send_to(&api)
+            .await
+            .unwrap();
+
+        assert_eq!(response.data[0].name, "angry");
+    }
+
+    #[tokio::test]
+    async fn api_autocomplete_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/gifs/search/tags.*api_key=.+q=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-autocomplete-response.json")
+        .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::tags::AutocompleteRequest::new("rag")
+            .send_to(&api)
+            .await
+            .unwrap();
+
+        assert!(!response.data.is_empty());
+    }
+
+    #[tokio::test]
+    async fn api_trending_searches_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/trending/searches.*api_key=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-trending-searches-response.json")
+        .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::tags::TrendingSearchesRequest::new()
+            .send_to(&api)
+            .await
+            .unwrap();
+
+        assert!(!response.data.is_empty());
+    }
+
+    #[tokio::test]
+    async fn api_random_id_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock("GET", Matcher::Regex(r"/randomid.*api_key=.+".to_string()))
+            .with_status(200)
+            .with_body_from_file("data/example-random-id-response.json")
+            .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::randomid::RandomIdRequest::new()
+            .send_to(&api)
+            .await
+            .unwrap();
+
+        assert_eq!(response.data.random_id.as_str(), "e0771ad3f2b64f2d");
+    }
+
+    #[tokio::test]
+    async fn api_session_sends_random_id() {
+        let session_search = mock("GET", "/v1/gifs/search")
+            .match_query(Matcher::Regex(
+                "^api_key=[^&]+&q=tacos&random_id=e0771ad3f2b64f2d$".to_string(),
+            ))
+            .with_status(200)
+            .with_body_from_file("data/example-search-response.json")
+            .expect(1)
+            .create();
+        let own_search = mock("GET", "/v1/gifs/search")
+            .match_query(Matcher::Regex(
+                "^api_key=[^&]+&q=tacos&random_id=own$".to_string(),
+            ))
+            .with_status(200)
+            .with_body_from_file("data/example-search-response.json")
+            .expect(1)
+            .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(server_url(), "key".to_string(), client);
+        let random_id = v1::RandomId::from("e0771ad3f2b64f2d");
+        let session = api.session(random_id.clone());
+        assert_eq!(api.random_id(), None);
+        assert_eq!(session.random_id(), Some(&random_id));
+
+        v1::gifs::SearchRequest::new("tacos")
+            .send_to(&session)
+            .await
+            .unwrap();
+        v1::gifs::SearchRequest::new("tacos")
+            .with_random_id("own")
+            .send_to(&session)
+            .await
+            .unwrap();
+
+        session_search.assert();
+        own_search.assert();
+    }
+
+    #[tokio::test]
+    async fn api_emoji_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/v2/emoji\?.*api_key=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-emoji-response.json")
+        .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v2::emoji::EmojiRequest::new().send_to(&api).await.unwrap();
+
+        assert_eq!(response.pagination.next_cursor, Some(3));
+    }
+
+    #[tokio::test]
+    async fn api_emoji_variations_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/v2/emoji/13EjnL7RwHmA2Q/variations.*api_key=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-emoji-variations-response.json")
+        .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v2::emoji::EmojiVariationsRequest::new("13EjnL7RwHmA2Q")
+            .send_to(&api)
+            .await
+            .unwrap();
+
+        assert_eq!(response.data.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn api_category_tree() {
+        let api_root = server_url();
+        let _categories = mock("GET", "/v1/gifs/categories")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_body_from_file("data/example-categories-response.json")
+            .create();
+        let subcategories = mock(
+            "GET",
+            Matcher::Regex(r"^/v1/gifs/categories/\w+\?".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-subcategories-response.json")
+        .expect(2)
+        .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(api_root, "test-api-key".to_string(), client);
+
+        let categories = api.category_tree(2).await.unwrap();
+        assert_eq!(categories.len(), 2);
+        assert_eq!(categories[1].subcategories[0].name, "angry");
+        assert!(categories[1].subcategories[0].gif.is_some());
+        subcategories.assert();
+    }
+
+    #[tokio::test]
+    async fn api_channel_gifs() {
+        let api_root = server_url();
+        let _first = mock("GET", "/v1/channels/3142/gifs")
+            .match_query(Matcher::Regex("^api_key=[^&]+$".to_string()))
+            .with_status(200)
+            .with_body(search_page(0, 0, 7))
+            .create();
+        let _second = mock("GET", "/v1/channels/3142/gifs")
+            .match_query(Matcher::UrlEncoded("offset".to_string(), "5".to_string()))
+            .with_status(200)
+            .with_body(search_page(5, 2, 7))
+            .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(api_root, "test-api-key".to_string(), client);
+
+        let gifs = api.channel_gifs(3142).await.unwrap();
+
+        let ids: Vec<String> = gifs.into_iter().map(|gif| gif.id).collect();
+        let expected: Vec<String> = (0..7).map(|i| format!("gif{}", i)).collect();
+        assert_eq!(ids, expected);
+    }
+
+    #[tokio::test]
+    async fn api_upload_200_ok() {
+        let api_root = server_url();
+        let _m = mock("POST", "/v1/gifs")
+            .match_query(Matcher::UrlEncoded(
+                "api_key".to_string(),
+                "test-api-key".to_string(),
+            ))
+            .match_header(
+                "content-type",
+                Matcher::Regex("^multipart/form-data; boundary=".to_string()),
+            )
+            .match_body(Matcher::AllOf(vec![
+                Matcher::Regex(r#"name="file"; filename="tacos.gif"\r\n"#.to_string()),
+                Matcher::Regex("GIF89a".to_string()),
+                Matcher::Regex("tacos,food".to_string()),
+            ]))
+            .with_status(200)
+            .with_body_from_file("data/example-upload-response.json")
+            .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(api_root, "test-api-key".to_string(), client);
+
+        let response = v1::upload::UploadRequest::from_bytes("tacos.gif", &b"GIF89a"[..])
+            .with_tags(vec!["tacos", "food"])
+            .send_to(&api)
+            .await
+            .unwrap();
+
+        assert_eq!(response.data.id, "3o7TKMt1VVNkHV2PaE");
+    }
+
+    #[tokio::test]
+    async fn api_paginate_search() {
+        let api_root = server_url();
+        let _first = mock("GET", "/v1/gifs/search")
+            .match_query(Matcher::UrlEncoded("offset".to_string(), "0".to_string()))
+            .with_status(200)
+            .with_body(search_page(0, 0, 8))
+            .create();
+        let _second = mock("GET", "/v1/gifs/search")
+            .match_query(Matcher::UrlEncoded("offset".to_string(), "5".to_string()))
+            .with_status(200)
+            .with_body(search_page(5, 3, 8))
+            .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(api_root, "test-api-key".to_string(), client);
+
+        let ids = api
+            .paginate(v1::gifs::SearchRequest::new("rage").with_offset(0))
+            .map_ok(|g| g.id)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        let expected: Vec<String> = (0..8).map(|i| format!("gif{}", i)).collect();
+        assert_eq!(ids, expected);
+    }
+
+    #[tokio::test]
+    async fn api_paginate_max_items() {
+        let api_root = server_url();
+        let m = mock("GET", "/v1/gifs/trending")
+            .match_query(Matcher::UrlEncoded("offset".to_string(), "0".to_string()))
+            .with_status(200)
+            .with_body(search_page(0, 0, 100))
+            .expect(1)
+            .create();
+
+        let client = reqwest::Client::new();
+        let api = AsyncApi::new_with_url(api_root, "test-api-key".to_string(), client);
+
+        let gifs = api
+            .paginate(v1::gifs::TrendingRequest::new().with_offset(0))
+            .with_max_items(3)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(gifs.len(), 3);
+        m.assert();
+    }
+
+    #[tokio::test]
+    async fn api_retry_until_success() {
+        let api_root = server_url();
+        let failing = mock("GET", "/v1/gifs/retry-async?api_key=test-api-key")
+            .with_status(503)
+            .expect(2)
+            .create();
+        let rate_limited = mock("GET", "/v1/gifs/retry-async?api_key=test-api-key")
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .expect(1)
+            .create();
+        let ok = mock("GET", "/v1/gifs/retry-async?api_key=test-api-key")
+            .with_status(200)
+            .with_body_from_file("data/example-get-gif-response.json")
+            .expect(1)
+            .create();
+
+        let client = reqwest::Client::new();
+        let policy = RetryPolicy::new()
+            .with_max_attempts(4)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(5));
+        let api = AsyncApi::new_with_url(api_root, "test-api-key".to_string(), client)
+            .with_retry_policy(policy);
+
+        let response = v1::gifs::GetGifRequest::new("retry-async")
+            .send_to(&api)
+            .await
+            .unwrap();
+
+        assert!(response.meta.status == 200);
+        failing.assert();
+        rate_limited.assert();
+        ok.assert();
+    }
+
+    #[tokio::test]
+    async fn api_retry_gives_up() {
+        let api_root = server_url();
+        let failing = mock("GET", "/v1/gifs/retry-async-fail?api_key=test-api-key")
+            .with_status(500)
+            .expect(3)
+            .create();
+
+        let client = reqwest::Client::new();
+        let policy = RetryPolicy::new()
+            .with_max_attempts(3)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(5));
+        let api = AsyncApi::new_with_url(api_root, "test-api-key".to_string(), client)
+            .with_retry_policy(policy);
+
+        let err = v1::gifs::GetGifRequest::new("retry-async-fail")
+            .send_to(&api)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.status(), Some(500));
+        failing.assert();
+    }
+
+    #[tokio::test]
+    async fn api_retry_custom_sleep() {
+        let api_root = server_url();
+        let failing = mock("GET", "/v1/gifs/retry-async-sleep?api_key=test-api-key")
+            .with_status(503)
+            .expect(2)
+            .create();
+
+        let delays = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = delays.clone();
+        let client = reqwest::Client::new();
+        let policy = RetryPolicy::new()
+            .with_max_attempts(2)
+            .with_backoff(Duration::from_secs(3600), Duration::from_secs(3600))
+            .with_jitter(false);
+        let api = AsyncApi::new_with_url(api_root, "test-api-key".to_string(), client)
+            .with_retry_policy(policy)
+            .with_sleep(move |delay| {
+                recorded.lock().unwrap().push(delay);
+                futures::future::ready(()).boxed()
+            });
+
+        let err = v1::gifs::GetGifRequest::new("retry-async-sleep")
+            .send_to(&api)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.status(), Some(503));
+        assert_eq!(*delays.lock().unwrap(), vec![Duration::from_secs(3600)]);
+        failing.assert();
+    }
+
+    #[tokio::test]
+    async fn api_rate_limiter_wait() {
+        let api_root = server_url();
+        let m = mock("GET", "/v1/gifs/limited-async?api_key=test-api-key")
+            .with_status(200)
+            .with_body_from_file("data/example-get-gif-response.json")
+            .expect(2)
+            .create();
+
+        let client = reqwest::Client::new();
+        let limiter = RateLimiter::new(1, Duration::from_millis(100));
+        let api = AsyncApi::new_with_url(api_root, "test-api-key".to_string(), client)
+            .with_rate_limiter(limiter);
+
+        let request = v1::gifs::GetGifRequest::new("limited-async");
+        let start = std::time::Instant::now();
+        request.send_to(&api).await.unwrap();
+        request.send_to(&api).await.unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(80));
+        m.assert();
+    }
+
+    #[tokio::test]
+    async fn api_cache_serves_repeated_requests() {
+        let api_root = server_url();
+        let ok = mock(
+            "GET",
+            Matcher::Regex(r"^/v1/gifs/cache-async\?api_key=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-get-gif-response.json")
+        .expect(1)
+        .create();
+
+        let cache = ResponseCache::in_memory(10);
+        let api = AsyncApi::new_with_url(
+            api_root.clone(),
+            "first-key".to_string(),
+            reqwest::Client::new(),
+        )
+        .with_cache(cache.clone());
+        let other_key_api =
+            AsyncApi::new_with_url(api_root, "second-key".to_string(), reqwest::Client::new())
+                .with_cache(cache);
+
+        for api in [&api, &other_key_api, &api] {
+            let response = v1::gifs::GetGifRequest::new("cache-async")
+                .send_to(api)
+                .await
+                .unwrap_or_else(|e| panic!("Error while calling get gif endpoint: {:?}", e));
+            assert_eq!(response.data.id, "xT4uQulxzV39haRFjG");
+        }
+        ok.assert();
+    }
 }
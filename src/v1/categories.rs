@@ -0,0 +1,240 @@
+//! Category endpoints, used to browse GIFs by topic
+//!
+//! Giphy categories are nested two levels deep. The whole tree can be fetched at once using
+//! `SyncApi::category_tree` or `AsyncApi::category_tree`.
+//!
+//! ```no_run
+//! use giphy::v1::sync::*;
+//!
+//! # let client = reqwest::blocking::Client::new();
+//! # let api = SyncApi::new("[your Giphy API key]".to_string(), client);
+//! for category in api.category_tree(2).unwrap() {
+//!     let names: Vec<_> = category.subcategories.iter().map(|c| c.name.as_str()).collect();
+//!     println!("{}: {}", category.name, names.join(", "));
+//! }
+//! ```
+
+use super::model::*;
+use std::convert::TryFrom;
+use std::time::Duration;
+
+/// Depth of the category tree returned by Giphy API
+pub const MAX_DEPTH: usize = 2;
+
+/// Marks category list requests that can be paged by offset
+pub trait CategoryListRequest: GiphyRequest<CategoryListResponse> {
+    /// Changes the offset of the first category returned by the request
+    fn set_offset(&mut self, offset: u32);
+}
+
+/// Giphy [Categories endpoint] request
+///
+/// [Categories endpoint]: https://developers.giphy.com/docs/api/endpoint#categories
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CategoriesRequest {
+    pub(crate) limit: Option<u32>,
+
+    pub(crate) offset: Option<u32>,
+}
+
+impl CategoriesRequest {
+    /// Creates new [Categories endpoint] request
+    ///
+    /// [Categories endpoint]: https://developers.giphy.com/docs/api/endpoint#categories
+    pub fn new() -> CategoriesRequest {
+        Default::default()
+    }
+
+    /// Limits the maximum number of categories returned from [Categories] request
+    ///
+    /// [Categories]: https://developers.giphy.com/docs/api/endpoint#categories
+    pub fn with_limit(mut self, value: u32) -> Self {
+        self.limit = Some(value);
+        self
+    }
+
+    /// Specifies the number of categories to skip when making [Categories] request
+    ///
+    /// [Categories]: https://developers.giphy.com/docs/api/endpoint#categories
+    pub fn with_offset(mut self, value: u32) -> Self {
+        self.offset = Some(value);
+        self
+    }
+}
+
+impl GiphyRequest<CategoryListResponse> for CategoriesRequest {
+    fn get_endpoint(&self) -> &str {
+        "v1/gifs/categories"
+    }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(Duration::from_secs(24 * 60 * 60))
+    }
+}
+
+impl CategoryListRequest for CategoriesRequest {
+    fn set_offset(&mut self, offset: u32) {
+        self.offset = Some(offset);
+    }
+}
+
+/// Giphy subcategories request, listing subcategories of a category
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "SubcategoriesParams")]
+pub struct SubcategoriesRequest {
+    pub(crate) category: String,
+
+    #[serde(skip)]
+    pub(crate) endpoint: String,
+
+    pub(crate) limit: Option<u32>,
+
+    pub(crate) offset: Option<u32>,
+}
+
+/// Serialized form of [`SubcategoriesRequest`], from which the endpoint is rebuilt
+#[derive(Deserialize)]
+struct SubcategoriesParams {
+    category: String,
+    limit: Option<u32>,
+    offset: Option<u32>,
+}
+
+impl From<SubcategoriesParams> for SubcategoriesRequest {
+    fn from(params: SubcategoriesParams) -> SubcategoriesRequest {
+        SubcategoriesRequest {
+            limit: params.limit,
+            offset: params.offset,
+            ..SubcategoriesRequest::new(&params.category)
+        }
+    }
+}
+
+impl SubcategoriesRequest {
+    /// Creates new subcategories request for a category with given encoded name
+    pub fn new(category: &str) -> SubcategoriesRequest {
+        SubcategoriesRequest {
+            category: category.to_string(),
+            endpoint: format!("v1/gifs/categories/{}", encode_path_segment(category)),
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// Limits the maximum number of subcategories returned from the request
+    pub fn with_limit(mut self, value: u32) -> Self {
+        self.limit = Some(value);
+        self
+    }
+
+    /// Specifies the number of subcategories to skip when making the request
+    pub fn with_offset(mut self, value: u32) -> Self {
+        self.offset = Some(value);
+        self
+    }
+}
+
+impl GiphyRequest<CategoryListResponse> for SubcategoriesRequest {
+    fn get_endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(Duration::from_secs(24 * 60 * 60))
+    }
+
+    fn path_params(&self) -> &'static [&'static str] {
+        &["category"]
+    }
+}
+
+impl CategoryListRequest for SubcategoriesRequest {
+    fn set_offset(&mut self, offset: u32) {
+        self.offset = Some(offset);
+    }
+}
+
+/// Returns the offset of the page following given category list page, if there is one
+pub(crate) fn next_offset(page: &CategoryListResponse) -> Option<u32> {
+    let pagination = page.pagination.as_ref()?;
+    let next = pagination.offset + pagination.count;
+    let past_total = pagination.total_count.is_some_and(|total| next >= total);
+    if pagination.count <= 0 || past_total {
+        return None;
+    }
+    u32::try_from(next).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn page(count: i32, total_count: i32, offset: i32) -> CategoryListResponse {
+        let mut page: CategoryListResponse =
+            serde_json::from_str(include_str!("../../data/example-categories-response.json"))
+                .unwrap();
+        page.pagination = Some(Pagination {
+            count,
+            total_count: Some(total_count),
+            offset,
+        });
+        page
+    }
+
+    #[test]
+    fn categories_request() {
+        let req = CategoriesRequest::new().with_limit(10).with_offset(20);
+
+        assert_eq!(req.get_endpoint(), "v1/gifs/categories");
+        assert_eq!(
+            serde_urlencoded::to_string(&req).unwrap(),
+            "limit=10&offset=20"
+        );
+    }
+
+    #[test]
+    fn subcategories_request() {
+        let mut req = SubcategoriesRequest::new("tv shows").with_limit(5);
+        req.set_offset(5);
+
+        assert_eq!(req.get_endpoint(), "v1/gifs/categories/tv%20shows");
+        assert_eq!(
+            serde_urlencoded::to_string(&req).unwrap(),
+            "category=tv+shows&limit=5&offset=5"
+        );
+        assert_eq!(req.path_params(), &["category"]);
+    }
+
+    #[test]
+    fn deserialize_subcategories_request() {
+        let req = SubcategoriesRequest::new("tv shows").with_limit(5);
+        let json = serde_json::to_string(&req).unwrap();
+        let req: SubcategoriesRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(req.category, "tv shows");
+        assert_eq!(req.limit, Some(5));
+        assert_eq!(req.get_endpoint(), "v1/gifs/categories/tv%20shows");
+    }
+
+    #[test]
+    fn category_response() {
+        let response: CategoryListResponse =
+            serde_json::from_str(include_str!("../../data/example-categories-response.json"))
+                .unwrap();
+
+        let category = &response.data[0];
+        assert_eq!(category.name, "Actions");
+        assert_eq!(category.name_encoded, "actions");
+        assert_eq!(category.subcategories.len(), 2);
+        assert!(category.subcategories[0].gif.is_none());
+        assert!(category.gif.is_some());
+    }
+
+    #[test]
+    fn next_page_offset() {
+        assert_eq!(next_offset(&page(2, 5, 0)), Some(2));
+        assert_eq!(next_offset(&page(2, 5, 2)), Some(4));
+        assert_eq!(next_offset(&page(1, 5, 4)), None);
+        assert_eq!(next_offset(&page(0, 5, 0)), None);
+        assert_eq!(next_offset(&page(2, 0, 0)), None);
+    }
+}
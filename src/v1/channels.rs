@@ -0,0 +1,224 @@
+//! Channel endpoints, used to find channels and list the GIFs they curate
+//!
+//! [`ChannelGifsRequest`] is a [`PaginatedRequest`], so all GIFs of a channel can be walked
+//! using `paginate`, or fetched at once using `SyncApi::channel_gifs` or
+//! `AsyncApi::channel_gifs`.
+//!
+//! ```no_run
+//! use giphy::v1::channels::ChannelSearchRequest;
+//! use giphy::v1::sync::*;
+//!
+//! # let client = reqwest::blocking::Client::new();
+//! # let api = SyncApi::new("[your Giphy API key]".to_string(), client);
+//! let channels = ChannelSearchRequest::new("the office").send_to(&api).unwrap();
+//! for channel in &channels.data {
+//!     let gifs = api.channel_gifs(channel.id).unwrap();
+//!     println!("{}: {} GIFs", channel.display_name, gifs.len());
+//! }
+//! ```
+//!
+//! [`ChannelGifsRequest`]: struct.ChannelGifsRequest.html
+//! [`PaginatedRequest`]: ../pagination/trait.PaginatedRequest.html
+
+use super::model::*;
+use super::pagination::PaginatedRequest;
+use std::borrow::Cow;
+
+/// Giphy [Channel Search endpoint] request
+///
+/// [Channel Search endpoint]: https://developers.giphy.com/docs/api/endpoint#channel-search
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChannelSearchRequest<'q> {
+    #[serde(rename = "q")]
+    pub(crate) query: Cow<'q, str>,
+
+    pub(crate) limit: Option<u32>,
+
+    pub(crate) offset: Option<u32>,
+}
+
+impl<'q> ChannelSearchRequest<'q> {
+    /// Creates new [Channel Search endpoint] request
+    ///
+    /// [Channel Search endpoint]: https://developers.giphy.com/docs/api/endpoint#channel-search
+    pub fn new(query: impl Into<Cow<'q, str>>) -> ChannelSearchRequest<'q> {
+        ChannelSearchRequest {
+            query: query.into(),
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// Limits the maximum number of channels returned from [Channel Search] request
+    ///
+    /// [Channel Search]: https://developers.giphy.com/docs/api/endpoint#channel-search
+    pub fn with_limit(mut self, value: u32) -> Self {
+        self.limit = Some(value);
+        self
+    }
+
+    /// Specifies the number of channels to skip when making [Channel Search] request
+    ///
+    /// [Channel Search]: https://developers.giphy.com/docs/api/endpoint#channel-search
+    pub fn with_offset(mut self, value: u32) -> Self {
+        self.offset = Some(value);
+        self
+    }
+
+    /// Converts the request into one owning its query, which can outlive the borrowed data
+    pub fn into_owned(self) -> ChannelSearchRequest<'static> {
+        ChannelSearchRequest {
+            query: Cow::Owned(self.query.into_owned()),
+            limit: self.limit,
+            offset: self.offset,
+        }
+    }
+}
+
+impl<'q> GiphyRequest<ChannelListResponse> for ChannelSearchRequest<'q> {
+    fn get_endpoint(&self) -> &str {
+        "v1/channels/search"
+    }
+}
+
+/// Giphy channel content request, listing GIFs of a channel
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "ChannelGifsParams")]
+pub struct ChannelGifsRequest {
+    pub(crate) channel_id: u64,
+
+    #[serde(skip)]
+    pub(crate) endpoint: String,
+
+    pub(crate) limit: Option<u32>,
+
+    pub(crate) offset: Option<u32>,
+}
+
+/// Serialized form of [`ChannelGifsRequest`], from which the endpoint is rebuilt
+#[derive(Deserialize)]
+struct ChannelGifsParams {
+    channel_id: u64,
+    limit: Option<u32>,
+    offset: Option<u32>,
+}
+
+impl From<ChannelGifsParams> for ChannelGifsRequest {
+    fn from(params: ChannelGifsParams) -> ChannelGifsRequest {
+        ChannelGifsRequest {
+            limit: params.limit,
+            offset: params.offset,
+            ..ChannelGifsRequest::new(params.channel_id)
+        }
+    }
+}
+
+impl ChannelGifsRequest {
+    /// Creates new request for GIFs of the channel with given id
+    pub fn new(channel_id: u64) -> ChannelGifsRequest {
+        ChannelGifsRequest {
+            channel_id,
+            endpoint: format!("v1/channels/{}/gifs", channel_id),
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// Limits the maximum number of GIF objects returned from the request
+    pub fn with_limit(mut self, value: u32) -> Self {
+        self.limit = Some(value);
+        self
+    }
+
+    /// Specifies the number of GIF objects to skip when making the request
+    pub fn with_offset(mut self, value: u32) -> Self {
+        self.offset = Some(value);
+        self
+    }
+}
+
+impl GiphyRequest<PaginatedGifListResponse> for ChannelGifsRequest {
+    fn get_endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    fn path_params(&self) -> &'static [&'static str] {
+        &["channel_id"]
+    }
+}
+
+impl PaginatedRequest for ChannelGifsRequest {
+    fn offset(&self) -> u32 {
+        self.offset.unwrap_or(0)
+    }
+
+    fn set_offset(&mut self, offset: u32) {
+        self.offset = Some(offset);
+    }
+
+    fn limit(&self) -> Option<u32> {
+        self.limit
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn channel_search_request() {
+        let req = ChannelSearchRequest::new("the office")
+            .with_limit(10)
+            .with_offset(5);
+
+        assert_eq!(req.get_endpoint(), "v1/channels/search");
+        assert_eq!(
+            serde_urlencoded::to_string(&req).unwrap(),
+            "q=the+office&limit=10&offset=5"
+        );
+    }
+
+    #[test]
+    fn channel_gifs_request() {
+        let mut req = ChannelGifsRequest::new(3142).with_limit(25);
+        req.set_offset(50);
+
+        assert_eq!(req.get_endpoint(), "v1/channels/3142/gifs");
+        assert_eq!(req.offset(), 50);
+        assert_eq!(
+            serde_urlencoded::to_string(&req).unwrap(),
+            "channel_id=3142&limit=25&offset=50"
+        );
+        assert_eq!(req.path_params(), &["channel_id"]);
+    }
+
+    #[test]
+    fn deserialize_channel_gifs_request() {
+        let req = ChannelGifsRequest::new(3142).with_offset(50);
+        let json = serde_json::to_string(&req).unwrap();
+        let req: ChannelGifsRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(req.channel_id, 3142);
+        assert_eq!(req.offset(), 50);
+        assert_eq!(req.get_endpoint(), "v1/channels/3142/gifs");
+    }
+
+    #[test]
+    fn channel_response() {
+        let response: ChannelListResponse = serde_json::from_str(include_str!(
+            "../../data/example-channels-search-response.json"
+        ))
+        .unwrap();
+
+        let channel = &response.data[0];
+        assert_eq!(channel.id, 3142);
+        assert_eq!(channel.slug, "theoffice");
+        assert_eq!(channel.display_name, "The Office");
+        assert!(channel.user.is_some());
+        assert!(channel.featured_gif.is_some());
+        assert!(channel.is_visible);
+        assert_eq!(channel.parent, None);
+        assert_eq!(channel.children[0].parent, Some(3142));
+        assert_eq!(channel.extra["type"], "community");
+        assert!(!response.data[1].is_visible);
+    }
+}
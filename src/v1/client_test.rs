@@ -0,0 +1,159 @@
+//! Test suite shared by [`SyncApi`] and [`AsyncApi`], making sure both clients report
+//! the same errors for the same responses
+
+use mockito::{mock, server_url, Mock};
+use std::time::Duration;
+
+use super::gifs::GetGifRequest;
+use super::r#async::{AsyncApi, RunnableAsyncRequest};
+use super::sync::{RunnableSyncRequest, SyncApi};
+use super::SingleGifResponse;
+use crate::GiphyError;
+
+fn mock_status(gif_id: &str, status: usize, body: &str) -> Mock {
+    mock(
+        "GET",
+        format!("/v1/gifs/{}?api_key=test-api-key", gif_id).as_str(),
+    )
+    .with_status(status)
+    .with_header("content-type", "application/json")
+    .with_body(body)
+    .expect(2)
+}
+
+/// Sends the same request using both clients and returns errors reported by each of them
+fn send_with_both_clients(gif_id: &str) -> (GiphyError, GiphyError) {
+    let request = GetGifRequest::new(gif_id);
+
+    let sync_api = SyncApi::new_with_url(
+        server_url(),
+        "test-api-key".to_string(),
+        reqwest::blocking::Client::new(),
+    );
+    let sync_result: Result<SingleGifResponse, _> =
+        RunnableSyncRequest::send_to(&request, &sync_api);
+
+    let async_api = AsyncApi::new_with_url(
+        server_url(),
+        "test-api-key".to_string(),
+        reqwest::Client::new(),
+    );
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let async_result: Result<SingleGifResponse, _> =
+        runtime.block_on(RunnableAsyncRequest::send_to(&request, &async_api));
+
+    match (sync_result, async_result) {
+        (Err(sync_err), Err(async_err)) => {
+            assert_eq!(sync_err.status(), async_err.status());
+            assert_eq!(sync_err.to_string(), async_err.to_string());
+            (sync_err, async_err)
+        }
+        results => panic!("Expected both clients to fail, got {:?}", results),
+    }
+}
+
+#[test]
+fn status_400_is_invalid_request() {
+    let m = mock_status(
+        "status400",
+        400,
+        r#"{"data":[],"meta":{"msg":"Bad Request","status":400,"response_id":"abc"}}"#,
+    )
+    .create();
+
+    for err in <[GiphyError; 2]>::from(send_with_both_clients("status400")) {
+        match err {
+            GiphyError::InvalidRequest {
+                reason,
+                status,
+                meta,
+            } => {
+                assert_eq!(reason, "Bad Request");
+                assert_eq!(status, Some(400));
+                assert_eq!(meta.map(|m| m.status), Some(400));
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+    m.assert();
+}
+
+#[test]
+fn status_403_is_http_status_without_meta() {
+    let m = mock_status(
+        "status403",
+        403,
+        r#"{"message":"Invalid authentication credentials"}"#,
+    )
+    .create();
+
+    for err in <[GiphyError; 2]>::from(send_with_both_clients("status403")) {
+        match err {
+            GiphyError::HttpStatus { status, meta, body } => {
+                assert_eq!(status, 403);
+                assert!(meta.is_none());
+                assert!(body.contains("Invalid authentication credentials"));
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+    m.assert();
+}
+
+#[test]
+fn status_404_is_http_status_with_meta() {
+    let m = mock_status(
+        "status404",
+        404,
+        r#"{"data":[],"meta":{"msg":"Not Found","status":404,"response_id":"abc"}}"#,
+    )
+    .create();
+
+    for err in <[GiphyError; 2]>::from(send_with_both_clients("status404")) {
+        match err {
+            GiphyError::HttpStatus { status, meta, .. } => {
+                assert_eq!(status, 404);
+                assert_eq!(meta.map(|m| m.msg), Some("Not Found".to_string()));
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+    m.assert();
+}
+
+#[test]
+fn status_429_is_rate_limited() {
+    let m = mock_status("status429", 429, r#"{"message":"API rate limit exceeded"}"#)
+        .with_header("retry-after", "60")
+        .create();
+
+    for err in <[GiphyError; 2]>::from(send_with_both_clients("status429")) {
+        match err {
+            GiphyError::RateLimited { retry_after, .. } => {
+                assert_eq!(retry_after, Some(Duration::from_secs(60)));
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+    m.assert();
+}
+
+#[test]
+fn status_500_is_http_status() {
+    let m = mock_status("status500", 500, "Internal Server Error").create();
+
+    for err in <[GiphyError; 2]>::from(send_with_both_clients("status500")) {
+        match err {
+            GiphyError::HttpStatus { status, meta, body } => {
+                assert_eq!(status, 500);
+                assert!(meta.is_none());
+                assert_eq!(body, "Internal Server Error");
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+    m.assert();
+}
@@ -0,0 +1,195 @@
+//! Typed access to [`Gif`] timestamps
+//!
+//! Giphy returns timestamps as `YYYY-MM-DD HH:MM:SS` strings in UTC, using
+//! `0000-00-00 00:00:00` or the Unix epoch when the event never happened, e.g. for GIFs
+//! that were never trending. With `chrono` or `time` feature enabled, the timestamps can
+//! be parsed into [`chrono::DateTime<Utc>`] or [`time::OffsetDateTime`] respectively,
+//! with the placeholder values mapped to `None`.
+//!
+//! ```
+//! # #[cfg(feature = "chrono")]
+//! # fn main() {
+//! use chrono::{DateTime, TimeZone, Utc};
+//! use giphy::v1::datetime;
+//! # let gifs: Vec<giphy::v1::Gif> = Vec::new();
+//!
+//! let since = Utc.with_ymd_and_hms(2019, 1, 1, 0, 0, 0).unwrap();
+//! for gif in datetime::trending_since(&gifs, &since) {
+//!     let trending: Option<DateTime<Utc>> = gif.trending_at();
+//!     println!("{} trending since {:?}", gif.id, trending);
+//! }
+//! # }
+//! # #[cfg(not(feature = "chrono"))]
+//! # fn main() {}
+//! ```
+//!
+//! [`Gif`]: ../struct.Gif.html
+//! [`chrono::DateTime<Utc>`]: https://docs.rs/chrono/0.4/chrono/struct.DateTime.html
+//! [`time::OffsetDateTime`]: https://docs.rs/time/0.3/time/struct.OffsetDateTime.html
+
+use std::cmp::Reverse;
+
+use super::model::Gif;
+
+/// Date and time types Giphy timestamps can be parsed into
+pub trait GiphyDateTime: Sized {
+    /// Parses a Giphy timestamp, returning `None` for placeholder or invalid values
+    fn parse_giphy(value: &str) -> Option<Self>;
+}
+
+/// Returns `true` for values Giphy uses when the event never happened
+fn is_placeholder(value: &str) -> bool {
+    let value = value.trim();
+    value.is_empty() || value.starts_with("0000-00-00") || value == "1970-01-01 00:00:00"
+}
+
+#[cfg(feature = "chrono")]
+impl GiphyDateTime for chrono::DateTime<chrono::Utc> {
+    fn parse_giphy(value: &str) -> Option<Self> {
+        if is_placeholder(value) {
+            return None;
+        }
+        chrono::NaiveDateTime::parse_from_str(value.trim(), "%Y-%m-%d %H:%M:%S")
+            .ok()
+            .map(|datetime| datetime.and_utc())
+    }
+}
+
+#[cfg(feature = "time")]
+impl GiphyDateTime for time::OffsetDateTime {
+    fn parse_giphy(value: &str) -> Option<Self> {
+        if is_placeholder(value) {
+            return None;
+        }
+        let format =
+            time::macros::format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
+        time::PrimitiveDateTime::parse(value.trim(), &format)
+            .ok()
+            .map(|datetime| datetime.assume_utc())
+    }
+}
+
+fn parse<T: GiphyDateTime>(value: &Option<String>) -> Option<T> {
+    value.as_deref().and_then(T::parse_giphy)
+}
+
+impl Gif {
+    /// Returns the time the GIF was imported to Giphy
+    pub fn imported_at<T: GiphyDateTime>(&self) -> Option<T> {
+        parse(&self.import_datetime)
+    }
+
+    /// Returns the time the GIF was last trending on Giphy
+    pub fn trending_at<T: GiphyDateTime>(&self) -> Option<T> {
+        parse(&self.trending_datetime)
+    }
+
+    /// Returns the time the GIF was created
+    pub fn created_at<T: GiphyDateTime>(&self) -> Option<T> {
+        parse(&self.create_datetime)
+    }
+
+    /// Returns the time the GIF was last updated
+    pub fn updated_at<T: GiphyDateTime>(&self) -> Option<T> {
+        parse(&self.update_datetime)
+    }
+
+    /// Returns `true` if the GIF was trending at or after given time
+    pub fn is_trending_since<T: GiphyDateTime + Ord>(&self, since: &T) -> bool {
+        self.trending_at::<T>()
+            .is_some_and(|trending| &trending >= since)
+    }
+}
+
+/// Returns GIFs trending at or after given time
+pub fn trending_since<'a, T>(gifs: &'a [Gif], since: &'a T) -> impl Iterator<Item = &'a Gif>
+where
+    T: GiphyDateTime + Ord,
+{
+    gifs.iter().filter(move |gif| gif.is_trending_since(since))
+}
+
+/// Sorts GIFs from the most recently trending, with GIFs that were never trending last
+///
+/// The type parameter selects the date and time type used to compare the timestamps.
+pub fn sort_by_trending<T: GiphyDateTime + Ord>(gifs: &mut [Gif]) {
+    gifs.sort_by_cached_key(|gif| Reverse(gif.trending_at::<T>()));
+}
+
+/// Sorts GIFs from the most recently imported, with GIFs without import time last
+pub fn sort_by_imported<T: GiphyDateTime + Ord>(gifs: &mut [Gif]) {
+    gifs.sort_by_cached_key(|gif| Reverse(gif.imported_at::<T>()));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::v1::PaginatedGifListResponse;
+
+    fn gifs() -> Vec<Gif> {
+        let response: PaginatedGifListResponse =
+            serde_json::from_str(include_str!("../../data/example-trending-response.json"))
+                .unwrap();
+        response.data
+    }
+
+    #[test]
+    fn placeholders() {
+        assert!(is_placeholder("0000-00-00 00:00:00"));
+        assert!(is_placeholder("1970-01-01 00:00:00"));
+        assert!(is_placeholder(""));
+        assert!(!is_placeholder("2019-03-27 20:59:36"));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_timestamps() {
+        use chrono::{DateTime, TimeZone, Utc};
+
+        assert_eq!(
+            DateTime::<Utc>::parse_giphy("2019-03-27 20:59:36"),
+            Some(Utc.with_ymd_and_hms(2019, 3, 27, 20, 59, 36).unwrap())
+        );
+        assert_eq!(DateTime::<Utc>::parse_giphy("0000-00-00 00:00:00"), None);
+        assert_eq!(DateTime::<Utc>::parse_giphy("yesterday"), None);
+
+        let mut gifs = gifs();
+        sort_by_trending::<DateTime<Utc>>(&mut gifs);
+        let trending: Vec<Option<DateTime<Utc>>> =
+            gifs.iter().map(|gif| gif.trending_at()).collect();
+        assert!(trending.windows(2).all(|w| w[0] >= w[1]));
+
+        let since = Utc.with_ymd_and_hms(2019, 1, 1, 0, 0, 0).unwrap();
+        assert!(trending_since(&gifs, &since).all(|gif| gif
+            .trending_at::<DateTime<Utc>>()
+            .is_some_and(|t| t >= since)));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_timestamps() {
+        use time::macros::datetime;
+        use time::OffsetDateTime;
+
+        assert_eq!(
+            OffsetDateTime::parse_giphy("2019-03-27 20:59:36"),
+            Some(datetime!(2019-03-27 20:59:36 UTC))
+        );
+        assert_eq!(OffsetDateTime::parse_giphy("0000-00-00 00:00:00"), None);
+
+        let since = datetime!(2019-01-01 00:00:00 UTC);
+        let gifs = gifs();
+        let count = gifs
+            .iter()
+            .filter(|gif| gif.is_trending_since(&since))
+            .count();
+        assert!(count > 0 && count < gifs.len());
+        assert_eq!(trending_since(&gifs, &since).count(), count);
+
+        let mut gifs = gifs;
+        sort_by_imported::<OffsetDateTime>(&mut gifs);
+        let imported: Vec<Option<OffsetDateTime>> =
+            gifs.iter().map(|gif| gif.imported_at()).collect();
+        assert!(imported.windows(2).all(|w| w[0] >= w[1]));
+    }
+}
@@ -1,28 +1,48 @@
 use super::model::*;
+use super::pagination::PaginatedRequest;
+use super::params::*;
+use std::borrow::Cow;
 use std::default::Default;
+use std::time::Duration;
 
 /// Giphy [Search endpoint] request
 ///
 /// [Search endpoint]: https://developers.giphy.com/docs/api/
-#[derive(Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SearchRequest<'p> {
     #[serde(rename = "q")]
-    pub(crate) query: &'p str,
+    pub(crate) query: Cow<'p, str>,
 
     pub(crate) limit: Option<u32>,
 
     pub(crate) offset: Option<u32>,
+
+    #[serde(serialize_with = "serialize_filter_rating")]
+    pub(crate) rating: Option<Rating>,
+
+    pub(crate) lang: Option<Language>,
+
+    pub(crate) random_id: Option<Cow<'p, str>>,
+
+    pub(crate) bundle: Option<Bundle>,
+
+    pub(crate) country_code: Option<CountryCode>,
 }
 
 impl<'p> SearchRequest<'p> {
     /// Creates new [Search endpoint] request
     ///
     /// [Search endpoint]: https://developers.giphy.com/docs/api/endpoint#search
-    pub fn new(query: &'p str) -> SearchRequest<'p> {
+    pub fn new(query: impl Into<Cow<'p, str>>) -> SearchRequest<'p> {
         SearchRequest {
-            query,
+            query: query.into(),
             limit: None,
             offset: None,
+            rating: None,
+            lang: None,
+            random_id: None,
+            bundle: None,
+            country_code: None,
         }
     }
 
@@ -41,6 +61,60 @@ impl<'p> SearchRequest<'p> {
         self.offset = Some(value);
         self
     }
+
+    /// Specifies the rating of GIF objects returned from [Search] request
+    ///
+    /// [Search]: https://developers.giphy.com/docs/api/endpoint#search
+    pub fn with_rating(mut self, value: Rating) -> Self {
+        self.rating = Some(value);
+        self
+    }
+
+    /// Specifies the language of the query of [Search] request
+    ///
+    /// [Search]: https://developers.giphy.com/docs/api/endpoint#search
+    pub fn with_lang(mut self, value: Language) -> Self {
+        self.lang = Some(value);
+        self
+    }
+
+    /// Specifies the random ID of the user making [Search] request
+    ///
+    /// [Search]: https://developers.giphy.com/docs/api/endpoint#search
+    pub fn with_random_id(mut self, value: impl Into<Cow<'p, str>>) -> Self {
+        self.random_id = Some(value.into());
+        self
+    }
+
+    /// Specifies the rendition bundle of GIF objects returned from [Search] request
+    ///
+    /// [Search]: https://developers.giphy.com/docs/api/endpoint#search
+    pub fn with_bundle(mut self, value: Bundle) -> Self {
+        self.bundle = Some(value);
+        self
+    }
+
+    /// Localizes the results of [Search] request to given country
+    ///
+    /// [Search]: https://developers.giphy.com/docs/api/endpoint#search
+    pub fn with_country_code(mut self, value: CountryCode) -> Self {
+        self.country_code = Some(value);
+        self
+    }
+
+    /// Converts the request into one owning its query, which can outlive the borrowed data
+    pub fn into_owned(self) -> SearchRequest<'static> {
+        SearchRequest {
+            query: Cow::Owned(self.query.into_owned()),
+            limit: self.limit,
+            offset: self.offset,
+            rating: self.rating,
+            lang: self.lang,
+            random_id: self.random_id.map(|id| Cow::Owned(id.into_owned())),
+            bundle: self.bundle,
+            country_code: self.country_code,
+        }
+    }
 }
 
 impl<'p> GiphyRequest<PaginatedGifListResponse> for SearchRequest<'p> {
@@ -49,30 +123,45 @@ impl<'p> GiphyRequest<PaginatedGifListResponse> for SearchRequest<'p> {
     }
 }
 
+impl<'p> PaginatedRequest for SearchRequest<'p> {
+    fn offset(&self) -> u32 {
+        self.offset.unwrap_or(0)
+    }
+
+    fn set_offset(&mut self, offset: u32) {
+        self.offset = Some(offset);
+    }
+
+    fn limit(&self) -> Option<u32> {
+        self.limit
+    }
+}
+
 /// Giphy [Trending endpoint] request
 ///
 /// [Trending endpoint]: https://developers.giphy.com/docs/api/endpoint#trending
-#[derive(Serialize, Default)]
-pub struct TrendingRequest<'a> {
-    pub(crate) rating: Option<&'a str>,
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TrendingRequest {
+    #[serde(serialize_with = "serialize_filter_rating")]
+    pub(crate) rating: Option<Rating>,
 
     pub(crate) limit: Option<u32>,
 
     pub(crate) offset: Option<u32>,
 }
 
-impl<'a> TrendingRequest<'a> {
+impl TrendingRequest {
     /// Creates new [Trending endpoint] request
     ///
     /// [Trending endpoint]: https://developers.giphy.com/docs/api/endpoint#trending
-    pub fn new() -> TrendingRequest<'a> {
+    pub fn new() -> TrendingRequest {
         Default::default()
     }
 
     /// Specifies the rating of GIF objects returned from [Trending] request
     ///
     /// [Trending]: https://developers.giphy.com/docs/api/endpoint#trending
-    pub fn with_rating<'b: 'a>(mut self, rating: &'b str) -> Self {
+    pub fn with_rating(mut self, rating: Rating) -> Self {
         self.rating = Some(rating);
         self
     }
@@ -94,19 +183,37 @@ impl<'a> TrendingRequest<'a> {
     }
 }
 
-impl<'p> GiphyRequest<PaginatedGifListResponse> for TrendingRequest<'p> {
+impl GiphyRequest<PaginatedGifListResponse> for TrendingRequest {
     fn get_endpoint(&self) -> &'static str {
         "v1/gifs/trending"
     }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(Duration::from_secs(10 * 60))
+    }
+}
+
+impl PaginatedRequest for TrendingRequest {
+    fn offset(&self) -> u32 {
+        self.offset.unwrap_or(0)
+    }
+
+    fn set_offset(&mut self, offset: u32) {
+        self.offset = Some(offset);
+    }
+
+    fn limit(&self) -> Option<u32> {
+        self.limit
+    }
 }
 
 /// Giphy [Translate endpoint] request
 ///
 /// [Translate endpoint]: https://developers.giphy.com/docs/api/endpoint#translate
-#[derive(Serialize, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct TranslateRequest<'a> {
     #[serde(rename = "s")]
-    pub(crate) phrase: &'a str,
+    pub(crate) phrase: Cow<'a, str>,
 
     pub(crate) weirdness: Option<u8>,
 }
@@ -115,9 +222,9 @@ impl<'a> TranslateRequest<'a> {
     /// Creates new [Translate endpoint] request
     ///
     /// [Translate endpoint]: https://developers.giphy.com/docs/api/endpoint#translate
-    pub fn new(phrase: &'a str) -> TranslateRequest {
+    pub fn new(phrase: impl Into<Cow<'a, str>>) -> TranslateRequest<'a> {
         TranslateRequest {
-            phrase,
+            phrase: phrase.into(),
             weirdness: None,
         }
     }
@@ -129,6 +236,14 @@ impl<'a> TranslateRequest<'a> {
         self.weirdness = Some(value);
         self
     }
+
+    /// Converts the request into one owning its phrase, which can outlive the borrowed data
+    pub fn into_owned(self) -> TranslateRequest<'static> {
+        TranslateRequest {
+            phrase: Cow::Owned(self.phrase.into_owned()),
+            weirdness: self.weirdness,
+        }
+    }
 }
 
 impl<'p> GiphyRequest<SingleGifResponse> for TranslateRequest<'p> {
@@ -140,39 +255,48 @@ impl<'p> GiphyRequest<SingleGifResponse> for TranslateRequest<'p> {
 /// Giphy [Random endpoint] request
 ///
 /// [Random endpoint]: https://developers.giphy.com/docs/api/
-#[derive(Serialize, Default)]
-pub struct RandomRequest<'a, 'b> {
-    pub(crate) tag: Option<&'a str>,
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RandomRequest<'a> {
+    pub(crate) tag: Option<Cow<'a, str>>,
 
-    pub(crate) rating: Option<&'b str>,
+    #[serde(serialize_with = "serialize_filter_rating")]
+    pub(crate) rating: Option<Rating>,
 }
 
-impl<'a, 'b> RandomRequest<'a, 'b> {
+impl<'a> RandomRequest<'a> {
     /// Creates new [Random endpoint] request
     ///
     /// [Random endpoint]: https://developers.giphy.com/docs/api/endpoint#random
-    pub fn new() -> RandomRequest<'a, 'b> {
+    pub fn new() -> RandomRequest<'a> {
         Default::default()
     }
 
     /// Filters [Random] request by specific tag
     ///
     /// [Random]: https://developers.giphy.com/docs/api/endpoint#random
-    pub fn with_tag(mut self, value: &'a str) -> Self {
-        self.tag = Some(value);
+    pub fn with_tag(mut self, value: impl Into<Cow<'a, str>>) -> Self {
+        self.tag = Some(value.into());
         self
     }
 
     /// Filters [Random] request by specific rating
     ///
     /// [Random]: https://developers.giphy.com/docs/api/endpoint#random
-    pub fn with_rating(mut self, value: &'b str) -> Self {
+    pub fn with_rating(mut self, value: Rating) -> Self {
         self.rating = Some(value);
         self
     }
+
+    /// Converts the request into one owning its tag, which can outlive the borrowed data
+    pub fn into_owned(self) -> RandomRequest<'static> {
+        RandomRequest {
+            tag: self.tag.map(|tag| Cow::Owned(tag.into_owned())),
+            rating: self.rating,
+        }
+    }
 }
 
-impl<'a, 'b> GiphyRequest<SingleGifResponse> for RandomRequest<'a, 'b> {
+impl<'a> GiphyRequest<SingleGifResponse> for RandomRequest<'a> {
     fn get_endpoint(&self) -> &'static str {
         "v1/gifs/random"
     }
@@ -181,19 +305,35 @@ impl<'a, 'b> GiphyRequest<SingleGifResponse> for RandomRequest<'a, 'b> {
 /// Giphy [GIF by id endpoint] request
 ///
 /// [GIF by id endpoint]: https://developers.giphy.com/docs/api/endpoint#get-gif-by-id
-#[derive(Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "GetGifParams")]
 pub struct GetGifRequest {
+    pub(crate) gif_id: String,
+
     #[serde(skip)]
     pub(crate) endpoint: String,
 }
 
+/// Serialized form of [`GetGifRequest`], from which the endpoint is rebuilt
+#[derive(Deserialize)]
+struct GetGifParams {
+    gif_id: String,
+}
+
+impl From<GetGifParams> for GetGifRequest {
+    fn from(params: GetGifParams) -> GetGifRequest {
+        GetGifRequest::new(&params.gif_id)
+    }
+}
+
 impl GetGifRequest {
     /// Created new [GIF by id] request
     ///
     /// [GIF by id]: https://developers.giphy.com/docs/api/endpoint#get-gif-by-id
     pub fn new(gif_id: &str) -> GetGifRequest {
         GetGifRequest {
-            endpoint: format!("v1/gifs/{}", gif_id),
+            gif_id: gif_id.to_string(),
+            endpoint: format!("v1/gifs/{}", encode_path_segment(gif_id)),
         }
     }
 }
@@ -202,12 +342,20 @@ impl GiphyRequest<SingleGifResponse> for GetGifRequest {
     fn get_endpoint(&self) -> &str {
         &self.endpoint
     }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(Duration::from_secs(24 * 60 * 60))
+    }
+
+    fn path_params(&self) -> &'static [&'static str] {
+        &["gif_id"]
+    }
 }
 
 /// Giphy [GIFs by id endpoint] request
 ///
 /// [GIFs by id endpoint]: https://developers.giphy.com/docs/api/endpoint#get-gif-by-id
-#[derive(Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GetGifsRequest {
     pub(crate) ids: String,
 }
@@ -225,6 +373,10 @@ impl GiphyRequest<PaginatedGifListResponse> for GetGifsRequest {
     fn get_endpoint(&self) -> &str {
         "v1/gifs"
     }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(Duration::from_secs(24 * 60 * 60))
+    }
 }
 
 #[cfg(test)]
@@ -241,15 +393,34 @@ mod test {
         assert_eq!(req.offset, Some(5));
     }
 
+    #[test]
+    fn search_request_query() {
+        let req = SearchRequest::new("hello there");
+        assert_eq!(serde_urlencoded::to_string(&req).unwrap(), "q=hello+there");
+
+        let req = req
+            .with_limit(10)
+            .with_rating(Rating::Pg13)
+            .with_lang("zh-tw".parse().unwrap())
+            .with_random_id("e0771ad3f2b64f2d")
+            .with_bundle(Bundle::MessagingNonClips)
+            .with_country_code("us".parse().unwrap());
+        assert_eq!(
+            serde_urlencoded::to_string(&req).unwrap(),
+            "q=hello+there&limit=10&rating=pg-13&lang=zh-TW&random_id=e0771ad3f2b64f2d\
+             &bundle=messaging_non_clips&country_code=US"
+        );
+    }
+
     #[test]
     fn trending_request() {
         let req = TrendingRequest::new()
-            .with_rating("g")
+            .with_rating(Rating::G)
             .with_limit(100)
             .with_offset(5);
 
         assert_eq!(req.get_endpoint(), "v1/gifs/trending");
-        assert_eq!(req.rating, Some("g"));
+        assert_eq!(req.rating, Some(Rating::G));
         assert_eq!(req.limit, Some(100));
         assert_eq!(req.offset, Some(5));
     }
@@ -265,17 +436,22 @@ mod test {
 
     #[test]
     fn random_request() {
-        let req = RandomRequest::new().with_tag("burrito").with_rating("g");
+        let req = RandomRequest::new()
+            .with_tag("burrito")
+            .with_rating(Rating::G);
 
         assert_eq!(req.get_endpoint(), "v1/gifs/random");
-        assert_eq!(req.tag, Some("burrito"));
-        assert_eq!(req.rating, Some("g"));
+        assert_eq!(req.tag.as_deref(), Some("burrito"));
+        assert_eq!(req.rating, Some(Rating::G));
     }
 
     #[test]
     fn get_gif_request() {
         let req = GetGifRequest::new("xT4uQulxzV39haRFjG");
         assert_eq!(req.get_endpoint(), "v1/gifs/xT4uQulxzV39haRFjG");
+
+        let req = GetGifRequest::new("../search?q=x");
+        assert_eq!(req.get_endpoint(), "v1/gifs/..%2Fsearch%3Fq%3Dx");
     }
 
     #[test]
@@ -285,4 +461,49 @@ mod test {
         assert_eq!(req.get_endpoint(), "v1/gifs");
         assert_eq!(req.ids, "xT4uQulxzV39haRFjG,3og0IPxMM0erATueVW");
     }
+
+    #[test]
+    fn owned_requests() {
+        fn assert_owned<T: Clone + Send + 'static>(_: &T) {}
+
+        let query = String::from("cats");
+        let req = SearchRequest::new(&*query).with_limit(10).into_owned();
+        drop(query);
+        assert_owned(&req);
+
+        let req = std::thread::spawn(move || req.with_offset(20))
+            .join()
+            .unwrap();
+        assert_eq!(req.query, "cats");
+        assert_eq!(req.offset, Some(20));
+
+        let random = RandomRequest::new().with_tag(String::from("burrito"));
+        assert_owned(&random);
+        assert_owned(&TranslateRequest::new("rage").into_owned());
+    }
+
+    #[test]
+    fn deserialize_requests() {
+        let req: SearchRequest<'static> =
+            serde_json::from_str(r#"{"q": "cats", "limit": 10, "offset": null}"#).unwrap();
+        assert_eq!(req.query, "cats");
+        assert_eq!(req.limit, Some(10));
+
+        let req: TrendingRequest = serde_json::from_str(r#"{"rating": "pg-13"}"#).unwrap();
+        assert_eq!(req.rating, Some(Rating::Pg13));
+
+        let req = RandomRequest::new()
+            .with_tag("burrito")
+            .with_rating(Rating::G);
+        let json = serde_json::to_string(&req).unwrap();
+        let req: RandomRequest<'static> = serde_json::from_str(&json).unwrap();
+        assert_eq!(req.tag.as_deref(), Some("burrito"));
+        assert_eq!(req.rating, Some(Rating::G));
+
+        let req = GetGifRequest::new("xT4uQulxzV39haRFjG");
+        let json = serde_json::to_string(&req).unwrap();
+        let req: GetGifRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(req.gif_id, "xT4uQulxzV39haRFjG");
+        assert_eq!(req.get_endpoint(), "v1/gifs/xT4uQulxzV39haRFjG");
+    }
 }
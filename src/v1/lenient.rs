@@ -0,0 +1,434 @@
+//! Lenient deserialization of GIF responses
+//!
+//! Giphy does not specify which parts of the GIF object are optional, so a single GIF
+//! with an unexpected shape fails deserialization of the whole response. Wrapping a request
+//! in [`Lenient`] deserializes each GIF separately instead. Values that could not be
+//! deserialized are replaced with defaults, and GIFs that still can't be deserialized are
+//! skipped. Every such change is reported as a [`Warning`] in the response.
+//!
+//! ```no_run
+//! use giphy::v1::gifs::SearchRequest;
+//! use giphy::v1::lenient::{Lenient, LenientGifListResponse};
+//! use giphy::v1::sync::*;
+//!
+//! # let client = reqwest::blocking::Client::new();
+//! # let api = SyncApi::new("[your Giphy API key]".to_string(), client);
+//! let response: LenientGifListResponse = Lenient::new(SearchRequest::new("tacos"))
+//!     .send_to(&api)
+//!     .unwrap();
+//!
+//! for warning in &response.warnings {
+//!     eprintln!("{}", warning);
+//! }
+//! ```
+//!
+//! [`Lenient`]: struct.Lenient.html
+//! [`Warning`]: struct.Warning.html
+
+use serde::de::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::fmt;
+use std::time::Duration;
+
+use super::model::Pagination;
+use super::model::{ApiHost, Gif, GiphyRequest, Meta, PaginatedGifListResponse, SingleGifResponse};
+use crate::transport::{Method, RequestBody};
+
+/// Maximum number of values replaced in a single GIF before it is skipped
+const MAX_REPAIRS: usize = 32;
+
+/// Top-level GIF fields replaced with an empty string when missing or invalid
+const STRING_FIELDS: &[&str] = &[
+    "type",
+    "id",
+    "slug",
+    "url",
+    "bitly_url",
+    "embed_url",
+    "username",
+    "source",
+    "source_tld",
+    "source_post_url",
+    "title",
+];
+
+/// Request adapter deserializing responses leniently
+///
+/// Works with requests returning [`PaginatedGifListResponse`], which return
+/// [`LenientGifListResponse`] instead, and requests returning [`SingleGifResponse`], which
+/// return [`LenientGifResponse`].
+///
+/// [`PaginatedGifListResponse`]: ../struct.PaginatedGifListResponse.html
+/// [`LenientGifListResponse`]: struct.LenientGifListResponse.html
+/// [`SingleGifResponse`]: ../struct.SingleGifResponse.html
+/// [`LenientGifResponse`]: struct.LenientGifResponse.html
+#[derive(Serialize)]
+#[serde(transparent)]
+pub struct Lenient<R> {
+    request: R,
+}
+
+impl<R> Lenient<R> {
+    /// Wraps the request
+    pub fn new(request: R) -> Lenient<R> {
+        Lenient { request }
+    }
+
+    /// Returns the wrapped request
+    pub fn into_inner(self) -> R {
+        self.request
+    }
+}
+
+impl<R> GiphyRequest<LenientGifListResponse> for Lenient<R>
+where
+    R: GiphyRequest<PaginatedGifListResponse>,
+{
+    fn get_endpoint(&self) -> &str {
+        self.request.get_endpoint()
+    }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        self.request.cache_ttl()
+    }
+
+    fn host(&self) -> ApiHost {
+        self.request.host()
+    }
+
+    fn method(&self) -> Method {
+        self.request.method()
+    }
+
+    fn body(&self) -> Option<RequestBody> {
+        self.request.body()
+    }
+
+    fn path_params(&self) -> &'static [&'static str] {
+        self.request.path_params()
+    }
+}
+
+impl<R> GiphyRequest<LenientGifResponse> for Lenient<R>
+where
+    R: GiphyRequest<SingleGifResponse>,
+{
+    fn get_endpoint(&self) -> &str {
+        self.request.get_endpoint()
+    }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        self.request.cache_ttl()
+    }
+
+    fn host(&self) -> ApiHost {
+        self.request.host()
+    }
+
+    fn method(&self) -> Method {
+        self.request.method()
+    }
+
+    fn body(&self) -> Option<RequestBody> {
+        self.request.body()
+    }
+
+    fn path_params(&self) -> &'static [&'static str] {
+        self.request.path_params()
+    }
+}
+
+/// What happened to a value that could not be deserialized
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WarningAction {
+    /// The value was replaced with a default
+    Defaulted,
+    /// The whole GIF was skipped
+    Skipped,
+}
+
+/// Problem encountered while deserializing a response leniently
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Warning {
+    /// JSON path of the value that could not be deserialized, e.g. `data[3].images.original`
+    pub path: String,
+    pub message: String,
+    pub action: WarningAction,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let action = match self.action {
+            WarningAction::Defaulted => "replaced with default",
+            WarningAction::Skipped => "skipped",
+        };
+        write!(f, "{}: {} ({})", self.path, self.message, action)
+    }
+}
+
+/// Paginated GIFs list response deserialized leniently
+#[derive(Debug)]
+pub struct LenientGifListResponse {
+    /// GIFs that could be deserialized, possibly with some values defaulted
+    pub data: Vec<Gif>,
+    pub pagination: Option<Pagination>,
+    pub meta: Meta,
+    pub warnings: Vec<Warning>,
+}
+
+/// Single GIF response deserialized leniently
+#[derive(Debug)]
+pub struct LenientGifResponse {
+    /// The GIF, or `None` if it could not be deserialized
+    pub data: Option<Gif>,
+    pub meta: Meta,
+    pub warnings: Vec<Warning>,
+}
+
+#[derive(Deserialize)]
+struct RawResponse {
+    #[serde(default)]
+    data: Value,
+    #[serde(default)]
+    pagination: Option<Value>,
+    meta: Meta,
+}
+
+impl<'de> Deserialize<'de> for LenientGifListResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawResponse::deserialize(deserializer)?;
+        let mut warnings = Vec::new();
+
+        let items = match raw.data {
+            Value::Array(items) => items,
+            Value::Null => Vec::new(),
+            item => vec![item],
+        };
+        let data = items
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, item)| parse_gif(item, &format!("data[{}]", i), &mut warnings))
+            .collect();
+
+        let pagination = raw.pagination.and_then(|pagination| {
+            serde_json::from_value(pagination)
+                .map_err(|e| {
+                    warnings.push(Warning {
+                        path: "pagination".to_string(),
+                        message: e.to_string(),
+                        action: WarningAction::Defaulted,
+                    })
+                })
+                .ok()
+        });
+
+        Ok(LenientGifListResponse {
+            data,
+            pagination,
+            meta: raw.meta,
+            warnings,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for LenientGifResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawResponse::deserialize(deserializer)?;
+        let mut warnings = Vec::new();
+        let data = parse_gif(raw.data, "data", &mut warnings);
+
+        Ok(LenientGifResponse {
+            data,
+            meta: raw.meta,
+            warnings,
+        })
+    }
+}
+
+/// Deserializes a GIF, replacing values that fail to deserialize until it succeeds
+fn parse_gif(mut item: Value, prefix: &str, warnings: &mut Vec<Warning>) -> Option<Gif> {
+    for _ in 0..MAX_REPAIRS {
+        let error = match serde_path_to_error::deserialize::<_, Gif>(&item) {
+            Ok(gif) => return Some(gif),
+            Err(e) => e,
+        };
+        let path = error.path().to_string();
+        let message = error.into_inner().to_string();
+
+        let repaired = repair(&mut item, &path, &message);
+        warnings.push(Warning {
+            path: match repaired.as_deref().unwrap_or(&path) {
+                "." => prefix.to_string(),
+                path => format!("{}.{}", prefix, path),
+            },
+            message,
+            action: match repaired {
+                Some(_) => WarningAction::Defaulted,
+                None => WarningAction::Skipped,
+            },
+        });
+        repaired.as_ref()?;
+    }
+
+    warnings.push(Warning {
+        path: prefix.to_string(),
+        message: "too many invalid values".to_string(),
+        action: WarningAction::Skipped,
+    });
+    None
+}
+
+/// Replaces the value at the error path with a default, returning the path of the replaced
+/// value, or `None` if the GIF can't be repaired
+fn repair(item: &mut Value, path: &str, message: &str) -> Option<String> {
+    let gif = item.as_object_mut()?;
+    let mut segments = path.split('.');
+    let field = match path {
+        // Errors of missing fields are reported at the parent object
+        "." => message
+            .strip_prefix("missing field `")
+            .and_then(|m| m.split('`').next())?,
+        _ => segments.next()?,
+    };
+
+    match field {
+        "images" => match segments.next() {
+            Some(rendition) if gif["images"].is_object() => {
+                gif["images"].as_object_mut()?.remove(rendition)?;
+                Some(format!("images.{}", rendition))
+            }
+            _ => {
+                gif.insert("images".to_string(), Value::Object(Default::default()));
+                Some("images".to_string())
+            }
+        },
+        "user"
+        | "rating"
+        | "update_datetime"
+        | "create_datetime"
+        | "import_datetime"
+        | "trending_datetime"
+        | "analytics"
+        | "analytics_response_payload" => {
+            gif.insert(field.to_string(), Value::Null);
+            Some(field.to_string())
+        }
+        field if STRING_FIELDS.contains(&field) => {
+            gif.insert(field.to_string(), Value::String(String::new()));
+            Some(field.to_string())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::v1::gifs::GetGifRequest;
+
+    fn search_response() -> Value {
+        serde_json::from_str(include_str!("../../data/example-search-response.json")).unwrap()
+    }
+
+    #[test]
+    fn valid_response_has_no_warnings() {
+        let response: LenientGifListResponse = serde_json::from_value(search_response()).unwrap();
+
+        assert_eq!(response.data.len(), 5);
+        assert!(response.pagination.is_some());
+        assert!(response.warnings.is_empty());
+    }
+
+    #[test]
+    fn invalid_values_defaulted() {
+        let mut response = search_response();
+        response["data"][1]["bitly_url"] = Value::Null;
+        response["data"][1]
+            .as_object_mut()
+            .unwrap()
+            .remove("source_post_url");
+        response["data"][2]["images"]["original"]["width"] = "wide".into();
+        response["data"][3]["user"] = "nobody".into();
+
+        let response: LenientGifListResponse = serde_json::from_value(response).unwrap();
+        assert_eq!(response.data.len(), 5);
+        assert_eq!(response.data[1].bitly_url, "");
+        assert_eq!(response.data[1].source_post_url, "");
+        assert!(response.data[2].images.original.is_none());
+        assert!(response.data[2].images.fixed_height.is_some());
+        assert!(response.data[3].user.is_none());
+
+        let paths: Vec<_> = response.warnings.iter().map(|w| w.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "data[1].bitly_url",
+                "data[1].source_post_url",
+                "data[2].images.original",
+                "data[3].user",
+            ]
+        );
+        assert!(response
+            .warnings
+            .iter()
+            .all(|w| w.action == WarningAction::Defaulted));
+    }
+
+    #[test]
+    fn broken_gif_skipped() {
+        let mut response = search_response();
+        response["data"][0] = "not a gif".into();
+        response["pagination"] = "unknown".into();
+
+        let response: LenientGifListResponse = serde_json::from_value(response).unwrap();
+        assert_eq!(response.data.len(), 4);
+        assert!(response.pagination.is_none());
+        assert_eq!(response.warnings.len(), 2);
+        assert_eq!(response.warnings[0].path, "data[0]");
+        assert_eq!(response.warnings[0].action, WarningAction::Skipped);
+        assert_eq!(response.warnings[1].path, "pagination");
+    }
+
+    #[test]
+    fn single_gif() {
+        let mut response: Value =
+            serde_json::from_str(include_str!("../../data/example-get-gif-response.json")).unwrap();
+        response["data"]["title"] = 42.into();
+
+        let response: LenientGifResponse = serde_json::from_value(response).unwrap();
+        assert_eq!(response.data.map(|gif| gif.title), Some(String::new()));
+        assert_eq!(response.warnings.len(), 1);
+        assert_eq!(response.warnings[0].path, "data.title");
+    }
+
+    #[test]
+    fn unknown_fields_kept() {
+        let mut response = search_response();
+        response["data"][1]["is_sticker"] = 0.into();
+        response["data"][1]["user"]["is_verified"] = true.into();
+
+        let response: LenientGifListResponse = serde_json::from_value(response).unwrap();
+        let gif = &response.data[1];
+        assert_eq!(gif.extra["is_sticker"], 0);
+        assert_eq!(gif.user.as_ref().unwrap().extra["is_verified"], true);
+    }
+
+    #[test]
+    fn forwards_path_params() {
+        let req = Lenient::new(GetGifRequest::new("xT4uQulxzV39haRFjG"));
+        let path_params = GiphyRequest::<LenientGifResponse>::path_params(&req);
+        assert_eq!(path_params, &["gif_id"]);
+        assert_eq!(
+            GiphyRequest::<LenientGifResponse>::get_endpoint(&req),
+            "v1/gifs/xT4uQulxzV39haRFjG"
+        );
+
+        let query: Vec<(String, String)> =
+            serde_urlencoded::from_str(&serde_urlencoded::to_string(&req).unwrap()).unwrap();
+        let query: Vec<_> = query
+            .iter()
+            .filter(|(name, _)| !path_params.contains(&name.as_str()))
+            .collect();
+        assert!(query.is_empty());
+    }
+}
@@ -3,9 +3,22 @@
 //! [Giphy API v1]: https://developers.giphy.com/docs/api/
 //! [`search`]: https://developers.giphy.com/docs/api/endpoint/#search
 
-
+pub mod analytics;
+pub mod categories;
+pub mod channels;
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub mod datetime;
 pub mod gifs;
+pub mod lenient;
 mod model;
+pub mod pagination;
+mod params;
+mod quoted;
+pub mod randomid;
+pub mod rendition;
+pub mod stickers;
+pub mod tags;
+pub mod upload;
 
 #[cfg(feature = "sync")]
 pub mod sync;
@@ -14,3 +27,7 @@ pub mod sync;
 pub mod r#async;
 
 pub use model::*;
+pub use params::*;
+
+#[cfg(all(test, feature = "sync", feature = "async", feature = "reqwest"))]
+mod client_test;
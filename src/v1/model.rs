@@ -1,15 +1,89 @@
-use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::de::{DeserializeOwned, Deserializer};
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use super::params::RandomId;
+use super::quoted;
+use crate::transport::{Method, RequestBody};
 
 /// Default API URL for Giphy v1 API
 pub static API_ROOT: &str = "https://api.giphy.com";
 
+/// Default URL for Giphy upload API
+pub static UPLOAD_ROOT: &str = "https://upload.giphy.com";
+
+/// Giphy API host a request is sent to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiHost {
+    /// The API root, [`API_ROOT`] by default
+    ///
+    /// [`API_ROOT`]: static.API_ROOT.html
+    Api,
+    /// The upload API root, [`UPLOAD_ROOT`] by default
+    ///
+    /// [`UPLOAD_ROOT`]: static.UPLOAD_ROOT.html
+    Upload,
+}
+
 /// Marks objects that can be sent to Giphy API endpoints
 pub trait GiphyRequest<ResponseType>: Serialize
 where
     ResponseType: DeserializeOwned,
 {
     fn get_endpoint(&self) -> &str;
+
+    /// Returns how long responses to the request can be cached, `None` disables caching
+    ///
+    /// Only used by clients with a `ResponseCache` attached.
+    fn cache_ttl(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Returns the host the request is sent to
+    fn host(&self) -> ApiHost {
+        ApiHost::Api
+    }
+
+    /// Returns the HTTP method used to send the request
+    ///
+    /// Only `GET` requests are cached and retried.
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    /// Returns the body sent with the request, serialized fields are sent in the query string
+    fn body(&self) -> Option<RequestBody> {
+        None
+    }
+
+    /// Returns the names of serialized fields that are part of the endpoint path
+    ///
+    /// These fields are left out of the query string.
+    fn path_params(&self) -> &'static [&'static str] {
+        &[]
+    }
+}
+
+/// Percent-encodes all characters except the unreserved ones, so the value can be used
+/// as a single URL path segment
+pub(crate) fn encode_path_segment(value: &str) -> String {
+    use std::fmt::Write;
+
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => {
+                let _ = write!(encoded, "%{:02X}", byte);
+            }
+        }
+    }
+    encoded
 }
 
 /// Giphy [`Meta`] object representation
@@ -28,7 +102,9 @@ pub struct Meta {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Pagination {
     pub count: i32,
-    pub total_count: i32,
+    /// Total number of objects, `None` for endpoints that don't report it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<i32>,
     pub offset: i32,
 }
 
@@ -43,6 +119,10 @@ pub struct User {
     pub username: String,
     pub display_name: String,
     pub twitter: Option<String>,
+
+    /// Fields not known to the library, by name
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Giphy Animated [`Images`] object representation
@@ -51,13 +131,44 @@ pub struct User {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ImageAnimated {
     pub url: Option<String>,
-    pub width: String,
-    pub height: String,
-    pub size: Option<String>,
+    #[serde(
+        with = "quoted::dimension",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub width: Option<u32>,
+    #[serde(
+        with = "quoted::dimension",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub height: Option<u32>,
+    #[serde(with = "quoted::size", default)]
+    pub size: Option<u64>,
     pub mp4: Option<String>,
-    pub mp4_size: Option<String>,
+    #[serde(with = "quoted::size", default)]
+    pub mp4_size: Option<u64>,
     pub webp: Option<String>,
-    pub webp_size: Option<String>,
+    #[serde(with = "quoted::size", default)]
+    pub webp_size: Option<u64>,
+    #[serde(with = "quoted::size", default)]
+    pub frames: Option<u64>,
+    pub hash: Option<String>,
+}
+
+impl ImageAnimated {
+    /// Returns the ratio of width to height, or `None` if the dimensions are unknown
+    pub fn aspect_ratio(&self) -> Option<f64> {
+        aspect_ratio(self.width, self.height)
+    }
+
+    /// Returns the total size in bytes of the GIF, MP4 and WebP files of the rendition
+    pub fn total_size(&self) -> u64 {
+        [self.size, self.mp4_size, self.webp_size]
+            .iter()
+            .flatten()
+            .sum()
+    }
 }
 
 /// Giphy Still [`Images`] object representation
@@ -66,8 +177,27 @@ pub struct ImageAnimated {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ImageStill {
     pub url: String,
-    pub width: String,
-    pub height: String,
+    #[serde(
+        with = "quoted::dimension",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub width: Option<u32>,
+    #[serde(
+        with = "quoted::dimension",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub height: Option<u32>,
+    #[serde(with = "quoted::size", default)]
+    pub size: Option<u64>,
+}
+
+impl ImageStill {
+    /// Returns the ratio of width to height, or `None` if the dimensions are unknown
+    pub fn aspect_ratio(&self) -> Option<f64> {
+        aspect_ratio(self.width, self.height)
+    }
 }
 
 /// Giphy Looping [`Images`] object representation
@@ -76,55 +206,327 @@ pub struct ImageStill {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ImageLooping {
     pub mp4: String,
+    #[serde(with = "quoted::size", default)]
+    pub mp4_size: Option<u64>,
 }
 
-/// Giphy MP4 Preview [`Images`] object representation
+/// Giphy MP4 [`Images`] object representation
+///
+/// Used by `preview`, `original_mp4`, `hd` and `4k` renditions.
 ///
 /// [`Images`]: https://developers.giphy.com/docs/#images-object
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ImagePreviewMp4 {
     pub mp4: String,
-    pub mp4_size: String,
-    pub width: String,
-    pub height: String,
+    #[serde(with = "quoted::size", default)]
+    pub mp4_size: Option<u64>,
+    #[serde(
+        with = "quoted::dimension",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub width: Option<u32>,
+    #[serde(
+        with = "quoted::dimension",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub height: Option<u32>,
 }
 
-/// Giphy GIF Preview [`Images`] object representation
+impl ImagePreviewMp4 {
+    /// Returns the ratio of width to height, or `None` if the dimensions are unknown
+    pub fn aspect_ratio(&self) -> Option<f64> {
+        aspect_ratio(self.width, self.height)
+    }
+}
+
+/// Giphy GIF and WebP Preview [`Images`] object representation
 ///
 /// [`Images`]: https://developers.giphy.com/docs/#images-object
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ImagePreviewGif {
     pub url: String,
-    pub size: String,
-    pub width: String,
-    pub height: String,
+    #[serde(with = "quoted::size", default)]
+    pub size: Option<u64>,
+    #[serde(
+        with = "quoted::dimension",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub width: Option<u32>,
+    #[serde(
+        with = "quoted::dimension",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub height: Option<u32>,
+}
+
+impl ImagePreviewGif {
+    /// Returns the ratio of width to height, or `None` if the dimensions are unknown
+    pub fn aspect_ratio(&self) -> Option<f64> {
+        aspect_ratio(self.width, self.height)
+    }
+}
+
+fn aspect_ratio(width: Option<u32>, height: Option<u32>) -> Option<f64> {
+    match (width?, height?) {
+        (0, _) | (_, 0) => None,
+        (width, height) => Some(f64::from(width) / f64::from(height)),
+    }
 }
 
 /// Giphy [`Images`] object representation
 ///
+/// All renditions are optional, as Giphy omits some of them depending on the GIF and
+/// the endpoint. Renditions not known to the library are kept in [`extra`].
+///
 /// [`Images`]: https://developers.giphy.com/docs/#images-object
+/// [`extra`]: #structfield.extra
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Images {
-    pub fixed_height: ImageAnimated,
-    pub fixed_height_still: ImageStill,
-    pub fixed_height_downsampled: ImageAnimated,
-    pub fixed_width: ImageAnimated,
-    pub fixed_width_still: ImageStill,
-    pub fixed_width_downsampled: ImageAnimated,
-    pub fixed_height_small: ImageAnimated,
-    pub fixed_height_small_still: ImageStill,
-    pub fixed_width_small: ImageAnimated,
-    pub fixed_width_small_still: ImageStill,
-    pub downsized: ImageAnimated,
-    pub downsized_still: ImageStill,
-    pub downsized_large: ImageAnimated,
-    pub downsized_medium: ImageAnimated,
-    pub downsized_small: ImageAnimated,
-    pub original: ImageAnimated,
-    pub original_still: ImageStill,
-    pub looping: ImageLooping,
-    pub preview: ImagePreviewMp4,
-    pub preview_gif: ImagePreviewGif,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_height: Option<ImageAnimated>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_height_still: Option<ImageStill>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_height_downsampled: Option<ImageAnimated>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_width: Option<ImageAnimated>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_width_still: Option<ImageStill>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_width_downsampled: Option<ImageAnimated>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_height_small: Option<ImageAnimated>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_height_small_still: Option<ImageStill>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_width_small: Option<ImageAnimated>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_width_small_still: Option<ImageStill>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downsized: Option<ImageAnimated>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downsized_still: Option<ImageStill>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downsized_large: Option<ImageAnimated>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downsized_medium: Option<ImageAnimated>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downsized_small: Option<ImageAnimated>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub original: Option<ImageAnimated>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub original_still: Option<ImageStill>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub original_mp4: Option<ImagePreviewMp4>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub looping: Option<ImageLooping>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview: Option<ImagePreviewMp4>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview_gif: Option<ImagePreviewGif>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview_webp: Option<ImagePreviewGif>,
+    #[serde(rename = "480w_still", skip_serializing_if = "Option::is_none")]
+    pub still_480w: Option<ImageStill>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hd: Option<ImagePreviewMp4>,
+    #[serde(rename = "4k", skip_serializing_if = "Option::is_none")]
+    pub uhd_4k: Option<ImagePreviewMp4>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<ImageAnimated>,
+
+    /// Renditions not known to the library, by name
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Images {
+    /// Returns the total size in bytes of all files in all renditions
+    ///
+    /// Useful to estimate the storage needed to mirror a GIF. Sizes missing from the
+    /// response and renditions kept in `extra` are not counted.
+    pub fn total_size(&self) -> u64 {
+        let animated = [
+            &self.fixed_height,
+            &self.fixed_height_downsampled,
+            &self.fixed_width,
+            &self.fixed_width_downsampled,
+            &self.fixed_height_small,
+            &self.fixed_width_small,
+            &self.downsized,
+            &self.downsized_large,
+            &self.downsized_medium,
+            &self.downsized_small,
+            &self.original,
+            &self.source,
+        ];
+        let still = [
+            &self.fixed_height_still,
+            &self.fixed_width_still,
+            &self.fixed_height_small_still,
+            &self.fixed_width_small_still,
+            &self.downsized_still,
+            &self.original_still,
+            &self.still_480w,
+        ];
+        let mp4 = [&self.original_mp4, &self.preview, &self.hd, &self.uhd_4k];
+        let preview = [&self.preview_gif, &self.preview_webp];
+
+        let animated: u64 = animated
+            .iter()
+            .copied()
+            .flatten()
+            .map(|i| i.total_size())
+            .sum();
+        let still: u64 = still.iter().copied().flatten().filter_map(|i| i.size).sum();
+        let mp4: u64 = mp4
+            .iter()
+            .copied()
+            .flatten()
+            .filter_map(|i| i.mp4_size)
+            .sum();
+        let preview: u64 = preview
+            .iter()
+            .copied()
+            .flatten()
+            .filter_map(|i| i.size)
+            .sum();
+        let looping = self.looping.as_ref().and_then(|i| i.mp4_size).unwrap_or(0);
+
+        animated + still + mp4 + preview + looping
+    }
+}
+
+/// Giphy content [rating]
+///
+/// Ratings are ordered from the most to the least restrictive, so `rating <= Rating::Pg`
+/// selects content rated at most PG. Ratings not known to the library are deserialized as
+/// [`Rating::Unknown`], which is ordered after all known ratings.
+///
+/// ```
+/// use giphy::v1::Rating;
+///
+/// assert!(Rating::G < Rating::Pg13);
+/// assert_eq!("pg-13".parse::<Rating>(), Ok(Rating::Pg13));
+/// assert!("pg13".parse::<Rating>().is_err());
+/// ```
+///
+/// [rating]: https://developers.giphy.com/docs/optional-settings/#rating
+/// [`Rating::Unknown`]: #variant.Unknown
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rating {
+    G,
+    Pg,
+    Pg13,
+    R,
+    /// Rating returned by Giphy API that is not known to the library
+    ///
+    /// Requests with an unknown rating fail with `GiphyError::InvalidRequest` instead of
+    /// returning unfiltered content.
+    Unknown(String),
+}
+
+impl Rating {
+    /// Returns the rating as used by Giphy API
+    pub fn as_str(&self) -> &str {
+        match self {
+            Rating::G => "g",
+            Rating::Pg => "pg",
+            Rating::Pg13 => "pg-13",
+            Rating::R => "r",
+            Rating::Unknown(rating) => rating,
+        }
+    }
+
+    fn known(rating: &str) -> Option<Rating> {
+        match rating.to_ascii_lowercase().as_str() {
+            "g" => Some(Rating::G),
+            "pg" => Some(Rating::Pg),
+            "pg-13" => Some(Rating::Pg13),
+            "r" => Some(Rating::R),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing a string that is not a known [`Rating`]
+///
+/// [`Rating`]: enum.Rating.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownRating(pub String);
+
+impl fmt::Display for UnknownRating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown rating \"{}\", expected g, pg, pg-13 or r",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownRating {}
+
+impl FromStr for Rating {
+    type Err = UnknownRating;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rating::known(s).ok_or_else(|| UnknownRating(s.to_string()))
+    }
+}
+
+impl Serialize for Rating {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Rating {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let rating = String::deserialize(deserializer)?;
+        Ok(Rating::known(&rating).unwrap_or(Rating::Unknown(rating)))
+    }
+}
+
+/// Serializes a rating used to filter request results, rejecting unknown ratings
+pub(crate) fn serialize_filter_rating<S: Serializer>(
+    rating: &Option<Rating>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match rating {
+        Some(Rating::Unknown(rating)) => Err(ser::Error::custom(UnknownRating(rating.clone()))),
+        Some(rating) => serializer.serialize_some(rating.as_str()),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Giphy [analytics] object representation
+///
+/// [analytics]: https://developers.giphy.com/docs/api/schema#analytics-object
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Analytics {
+    /// Pingback sent when the GIF is shown to the user
+    pub onload: Option<PingbackUrl>,
+    /// Pingback sent when the user clicks the GIF
+    pub onclick: Option<PingbackUrl>,
+    /// Pingback sent when the user sends or shares the GIF
+    pub onsent: Option<PingbackUrl>,
+}
+
+/// Giphy analytics pingback URL object representation
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PingbackUrl {
+    pub url: String,
 }
 
 /// Giphy [`Gif`] object representation
@@ -141,7 +543,7 @@ pub struct Gif {
     pub embed_url: String,
     pub username: String,
     pub source: String,
-    pub rating: Option<String>,
+    pub rating: Option<Rating>,
     pub user: Option<User>,
     pub source_tld: String,
     pub source_post_url: String,
@@ -151,6 +553,25 @@ pub struct Gif {
     pub trending_datetime: Option<String>,
     pub images: Images,
     pub title: String,
+    /// Payload identifying the GIF in analytics events
+    #[serde(default)]
+    pub analytics_response_payload: Option<String>,
+    /// URLs of analytics pingbacks to send when users interact with the GIF
+    #[serde(default)]
+    pub analytics: Option<Analytics>,
+
+    /// Fields not known to the library, by name
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Gif {
+    /// Returns `true` if the GIF has a known rating at most as restrictive as `max`
+    pub fn is_rated_at_most(&self, max: &Rating) -> bool {
+        self.rating
+            .as_ref()
+            .is_some_and(|rating| rating <= max && !matches!(rating, Rating::Unknown(_)))
+    }
 }
 
 /// Paginated GIFs list response object representation
@@ -177,3 +598,277 @@ pub struct SingleGifResponse {
     pub data: Gif,
     pub meta: Meta,
 }
+
+/// Giphy tag object representation
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Tag {
+    pub name: String,
+    /// Payload identifying the tag in analytics events
+    pub analytics_response_payload: Option<String>,
+}
+
+/// Tag list response object representation
+///
+/// Returned by the following endpoints:
+/// - [Related tags](https://developers.giphy.com/docs/api/endpoint#related-tags)
+/// - [Search suggestions](https://developers.giphy.com/docs/api/endpoint#search-suggestions)
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TagListResponse {
+    pub data: Vec<Tag>,
+    pub pagination: Option<Pagination>,
+    pub meta: Meta,
+}
+
+/// Trending search terms response object representation
+///
+/// Returned by the [Trending search terms](https://developers.giphy.com/docs/api/endpoint#trending-search-terms)
+/// endpoint.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TrendingSearchesResponse {
+    pub data: Vec<String>,
+    pub meta: Meta,
+}
+
+/// Random ID object, holding the identifier issued for a user
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RandomIdData {
+    pub random_id: RandomId,
+}
+
+/// Random ID response object representation
+///
+/// Returned by the [Random ID](https://developers.giphy.com/docs/api/endpoint#random-id)
+/// endpoint.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RandomIdResponse {
+    pub data: RandomIdData,
+    pub meta: Meta,
+}
+
+/// Giphy category object representation
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Category {
+    pub name: String,
+    /// Name of the category as used in category endpoint paths
+    pub name_encoded: String,
+    /// Subcategories, only listed with names for top-level categories
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subcategories: Vec<Category>,
+    /// GIF featured for the category
+    pub gif: Option<Gif>,
+}
+
+/// Category list response object representation
+///
+/// Returned by the following endpoints:
+/// - [Categories](https://developers.giphy.com/docs/api/endpoint#categories)
+/// - Subcategories of a category
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CategoryListResponse {
+    pub data: Vec<Category>,
+    pub pagination: Option<Pagination>,
+    pub meta: Meta,
+}
+
+/// Giphy channel object representation
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Channel {
+    pub id: u64,
+    pub slug: String,
+    pub display_name: String,
+    pub user: Option<User>,
+    /// GIF featured for the channel
+    pub featured_gif: Option<Gif>,
+    #[serde(default)]
+    pub is_visible: bool,
+    /// Id of the parent channel, `None` for top-level channels
+    pub parent: Option<u64>,
+    /// Child channels, when included in the response
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Channel>,
+
+    /// Fields not known to the library, by name
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Paginated channel list response object representation
+///
+/// Returned by the [Channel Search](https://developers.giphy.com/docs/api/endpoint#channel-search)
+/// endpoint.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ChannelListResponse {
+    pub data: Vec<Channel>,
+    pub pagination: Pagination,
+    pub meta: Meta,
+}
+
+/// Giphy uploaded GIF object representation
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UploadedGif {
+    pub id: String,
+}
+
+/// Upload response object representation
+///
+/// Returned by the [Upload](https://developers.giphy.com/docs/api/endpoint#upload) endpoint.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UploadResponse {
+    pub data: UploadedGif,
+    pub meta: Meta,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rating_order() {
+        assert!(Rating::G < Rating::Pg);
+        assert!(Rating::Pg < Rating::Pg13);
+        assert!(Rating::Pg13 < Rating::R);
+        assert!(Rating::R < Rating::Unknown("y".to_string()));
+    }
+
+    #[test]
+    fn rating_serde() {
+        let ratings: Vec<Rating> =
+            serde_json::from_str(r#"["g", "PG", "pg-13", "r", "y"]"#).unwrap();
+        assert_eq!(
+            ratings,
+            vec![
+                Rating::G,
+                Rating::Pg,
+                Rating::Pg13,
+                Rating::R,
+                Rating::Unknown("y".to_string())
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&ratings).unwrap(),
+            r#"["g","pg","pg-13","r","y"]"#
+        );
+    }
+
+    #[test]
+    fn gif_rated_at_most() {
+        let mut response: SingleGifResponse =
+            serde_json::from_str(include_str!("../../data/example-get-gif-response.json")).unwrap();
+        assert_eq!(response.data.rating, Some(Rating::Pg));
+        assert!(response.data.is_rated_at_most(&Rating::Pg));
+        assert!(!response.data.is_rated_at_most(&Rating::G));
+
+        response.data.rating = Some(Rating::Unknown("y".to_string()));
+        assert!(!response.data.is_rated_at_most(&Rating::R));
+    }
+
+    #[test]
+    fn image_numbers() {
+        let response: SingleGifResponse =
+            serde_json::from_str(include_str!("../../data/example-get-gif-response.json")).unwrap();
+        let images = &response.data.images;
+        let original = images.original.as_ref().unwrap();
+
+        assert_eq!(original.width, Some(400));
+        assert_eq!(original.height, Some(300));
+        assert_eq!(original.frames, Some(72));
+        assert_eq!(original.aspect_ratio(), Some(400.0 / 300.0));
+        assert_eq!(original.total_size(), 999302 + 348859 + 320684);
+        assert!(images.total_size() > original.total_size());
+    }
+
+    #[test]
+    fn image_missing_dimensions() {
+        let animated: ImageAnimated =
+            serde_json::from_str(r#"{"url": "https://media.giphy.com/a.gif", "height": "200"}"#)
+                .unwrap();
+        assert_eq!(animated.width, None);
+        assert_eq!(animated.height, Some(200));
+        assert_eq!(animated.aspect_ratio(), None);
+
+        let still: ImageStill =
+            serde_json::from_str(r#"{"url": "https://media.giphy.com/a_s.gif"}"#).unwrap();
+        assert_eq!((still.width, still.height), (None, None));
+
+        let preview: ImagePreviewMp4 =
+            serde_json::from_str(r#"{"mp4": "https://media.giphy.com/a.mp4", "height": 100}"#)
+                .unwrap();
+        assert_eq!(preview.width, None);
+
+        let preview: ImagePreviewGif =
+            serde_json::from_str(r#"{"url": "https://media.giphy.com/a.gif", "width": 100}"#)
+                .unwrap();
+        assert_eq!(preview.height, None);
+    }
+
+    #[test]
+    fn images_renditions() {
+        let response: SingleGifResponse =
+            serde_json::from_str(include_str!("../../data/example-get-gif-response.json")).unwrap();
+        let images = &response.data.images;
+
+        assert!(images.original_mp4.is_some());
+        assert!(images.preview_webp.is_some());
+        assert!(images.still_480w.is_some());
+        assert!(images.extra.is_empty());
+
+        let images: Images = serde_json::from_str(
+            r#"{
+                "original": {"url": "https://giphy.com/o.gif", "width": "10", "height": "5"},
+                "8k": {"mp4": "https://giphy.com/8k.mp4"}
+            }"#,
+        )
+        .unwrap();
+        assert!(images.original.is_some());
+        assert!(images.fixed_height.is_none());
+        assert_eq!(
+            images.extra["8k"]["mp4"],
+            serde_json::json!("https://giphy.com/8k.mp4")
+        );
+        assert_eq!(
+            serde_json::to_value(&images).unwrap()["8k"]["mp4"],
+            "https://giphy.com/8k.mp4"
+        );
+    }
+
+    #[test]
+    fn images_round_trip() {
+        for fixture in &[
+            include_str!("../../data/example-search-response.json"),
+            include_str!("../../data/example-trending-response.json"),
+        ] {
+            let response: PaginatedGifListResponse = serde_json::from_str(fixture).unwrap();
+            let expected: serde_json::Value = serde_json::from_str(fixture).unwrap();
+            let serialized = serde_json::to_value(&response).unwrap();
+
+            for (gif, expected) in serialized["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .zip(expected["data"].as_array().unwrap())
+            {
+                for (name, image) in gif["images"].as_object().unwrap() {
+                    for (field, value) in image.as_object().unwrap() {
+                        if !value.is_null() {
+                            assert_eq!(value, &expected["images"][name][field]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_filter_rating_rejected() {
+        #[derive(Serialize)]
+        struct Filter {
+            #[serde(serialize_with = "serialize_filter_rating")]
+            rating: Option<Rating>,
+        }
+
+        let encode = |rating| serde_urlencoded::to_string(Filter { rating });
+        assert_eq!(encode(Some(Rating::Pg13)).unwrap(), "rating=pg-13");
+        assert_eq!(encode(None).unwrap(), "");
+        assert!(encode(Some(Rating::Unknown("pg13".to_string()))).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//! Helpers for walking all pages of paginated Giphy API responses
+//!
+//! Requests implementing [`PaginatedRequest`] can be paged through using
+//! `SyncApi::paginate`, which returns an [`Iterator`], or `AsyncApi::paginate`,
+//! which returns a [`Stream`].
+//!
+//! [`PaginatedRequest`]: trait.PaginatedRequest.html
+//! [`Iterator`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html
+//! [`Stream`]: https://docs.rs/futures/0.3/futures/stream/trait.Stream.html
+
+use std::collections::{HashSet, VecDeque};
+
+use super::model::{Gif, GiphyRequest, PaginatedGifListResponse};
+
+/// The maximum offset accepted by Giphy API paginated endpoints
+pub const MAX_OFFSET: u32 = 4999;
+
+/// Marks requests returning [`PaginatedGifListResponse`] that can be paged by offset
+///
+/// [`PaginatedGifListResponse`]: ../struct.PaginatedGifListResponse.html
+pub trait PaginatedRequest: GiphyRequest<PaginatedGifListResponse> {
+    /// Returns the offset of the first object returned by the request
+    fn offset(&self) -> u32;
+
+    /// Changes the offset of the first object returned by the request
+    fn set_offset(&mut self, offset: u32);
+
+    /// Returns the maximum number of objects returned by the request, if it was specified
+    ///
+    /// Used to detect the last page of responses that don't report the total count.
+    fn limit(&self) -> Option<u32> {
+        None
+    }
+}
+
+/// Pagination state shared by the synchronous and asynchronous page walkers
+pub(crate) struct Paginator<R> {
+    request: R,
+    max_items: Option<usize>,
+    yielded: usize,
+    seen: HashSet<String>,
+    buffer: VecDeque<Gif>,
+    exhausted: bool,
+}
+
+impl<R: PaginatedRequest> Paginator<R> {
+    pub(crate) fn new(request: R) -> Paginator<R> {
+        Paginator {
+            request,
+            max_items: None,
+            yielded: 0,
+            seen: HashSet::new(),
+            buffer: VecDeque::new(),
+            exhausted: false,
+        }
+    }
+
+    pub(crate) fn set_max_items(&mut self, max_items: usize) {
+        self.max_items = Some(max_items);
+    }
+
+    fn is_capped(&self) -> bool {
+        self.max_items.is_some_and(|max| self.yielded >= max)
+    }
+
+    /// Returns the next already fetched GIF, if the item cap was not reached yet
+    pub(crate) fn next_buffered(&mut self) -> Option<Gif> {
+        if self.is_capped() {
+            return None;
+        }
+
+        let gif = self.buffer.pop_front()?;
+        self.yielded += 1;
+        Some(gif)
+    }
+
+    /// Returns the request for the next page, or `None` if there are no more pages to fetch
+    pub(crate) fn next_request(&self) -> Option<&R> {
+        if self.exhausted || self.is_capped() {
+            None
+        } else {
+            Some(&self.request)
+        }
+    }
+
+    /// Stops fetching any further pages
+    #[cfg(any(feature = "sync", feature = "async"))]
+    pub(crate) fn finish(&mut self) {
+        self.exhausted = true;
+    }
+
+    /// Buffers GIFs from a fetched page, skipping the ones already seen on previous pages
+    pub(crate) fn accept_page(&mut self, page: PaginatedGifListResponse) {
+        let count = page.pagination.count.max(0) as u32;
+        let next_offset = self.request.offset() + count;
+
+        for gif in page.data {
+            if self.seen.insert(gif.id.clone()) {
+                self.buffer.push_back(gif);
+            }
+        }
+
+        // Without the total count, a page shorter than the limit is the last one
+        let last_page = match page.pagination.total_count {
+            Some(total_count) => next_offset >= total_count.max(0) as u32,
+            None => self.request.limit().is_some_and(|limit| count < limit),
+        };
+        if count == 0 || last_page || next_offset > MAX_OFFSET {
+            self.exhausted = true;
+        } else {
+            self.request.set_offset(next_offset);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::v1::gifs::SearchRequest;
+
+    fn page(ids: &[&str], offset: i32, total_count: i32) -> PaginatedGifListResponse {
+        let mut response: PaginatedGifListResponse =
+            serde_json::from_str(include_str!("../../data/example-search-response.json")).unwrap();
+        let template = response.data.pop().unwrap();
+        response.data = ids
+            .iter()
+            .map(|id| {
+                let mut gif: Gif =
+                    serde_json::from_value(serde_json::to_value(&template).unwrap()).unwrap();
+                gif.id = id.to_string();
+                gif
+            })
+            .collect();
+        response.pagination.count = ids.len() as i32;
+        response.pagination.offset = offset;
+        response.pagination.total_count = Some(total_count);
+        response
+    }
+
+    fn drain(paginator: &mut Paginator<SearchRequest>) -> Vec<String> {
+        std::iter::from_fn(|| paginator.next_buffered())
+            .map(|gif| gif.id)
+            .collect()
+    }
+
+    #[test]
+    fn advances_offset_and_dedupes() {
+        let mut paginator = Paginator::new(SearchRequest::new("rage"));
+
+        paginator.accept_page(page(&["a", "b"], 0, 10));
+        assert_eq!(paginator.next_request().map(|r| r.offset()), Some(2));
+
+        paginator.accept_page(page(&["b", "c"], 2, 10));
+        assert_eq!(paginator.next_request().map(|r| r.offset()), Some(4));
+        assert_eq!(drain(&mut paginator), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn stops_at_total_count() {
+        let mut paginator = Paginator::new(SearchRequest::new("rage"));
+
+        paginator.accept_page(page(&["a", "b"], 0, 2));
+        assert!(paginator.next_request().is_none());
+        assert_eq!(drain(&mut paginator), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn without_total_count() {
+        let mut paginator = Paginator::new(SearchRequest::new("rage").with_limit(2));
+
+        let mut first = page(&["a", "b"], 0, 0);
+        first.pagination.total_count = None;
+        paginator.accept_page(first);
+        assert_eq!(paginator.next_request().map(|r| r.offset()), Some(2));
+
+        let mut short = page(&["c"], 2, 0);
+        short.pagination.total_count = None;
+        paginator.accept_page(short);
+        assert!(paginator.next_request().is_none());
+        assert_eq!(drain(&mut paginator), vec!["a", "b", "c"]);
+
+        let mut paginator = Paginator::new(SearchRequest::new("rage"));
+        let mut first = page(&["a", "b"], 0, 0);
+        first.pagination.total_count = None;
+        paginator.accept_page(first);
+        assert_eq!(paginator.next_request().map(|r| r.offset()), Some(2));
+
+        let mut empty = page(&[], 2, 0);
+        empty.pagination.total_count = None;
+        paginator.accept_page(empty);
+        assert!(paginator.next_request().is_none());
+    }
+
+    #[test]
+    fn stops_on_empty_page() {
+        let mut paginator = Paginator::new(SearchRequest::new("rage"));
+
+        paginator.accept_page(page(&[], 0, 10));
+        assert!(paginator.next_request().is_none());
+    }
+
+    #[test]
+    fn stops_at_max_offset() {
+        let mut paginator = Paginator::new(SearchRequest::new("rage").with_offset(4998));
+
+        paginator.accept_page(page(&["a", "b"], 4998, 10000));
+        assert!(paginator.next_request().is_none());
+    }
+
+    #[test]
+    fn respects_max_items() {
+        let mut paginator = Paginator::new(SearchRequest::new("rage"));
+        paginator.set_max_items(3);
+
+        paginator.accept_page(page(&["a", "b"], 0, 10));
+        assert!(paginator.next_request().is_some());
+        paginator.accept_page(page(&["c", "d"], 2, 10));
+
+        assert_eq!(drain(&mut paginator), vec!["a", "b", "c"]);
+        assert!(paginator.next_request().is_none());
+    }
+}
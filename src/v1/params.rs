@@ -0,0 +1,327 @@
+use serde::de::{self, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+/// [Language] of the search query, as a BCP-47 language tag
+///
+/// Accepts a two or three letter language code, optionally followed by a script and
+/// a region subtag. Tags are normalized to the canonical case, e.g. `zh-tw` becomes `zh-TW`.
+///
+/// ```
+/// use giphy::v1::Language;
+///
+/// let lang: Language = "zh-tw".parse().unwrap();
+/// assert_eq!(lang.as_str(), "zh-TW");
+/// assert!("english".parse::<Language>().is_err());
+/// ```
+///
+/// [Language]: https://developers.giphy.com/docs/optional-settings/#language-support
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Language(String);
+
+impl Language {
+    /// Returns the language tag as used by Giphy API
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing a string that is not a valid [`Language`] tag
+///
+/// [`Language`]: struct.Language.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidLanguage(pub String);
+
+impl fmt::Display for InvalidLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid language \"{}\", expected a BCP-47 tag like en or zh-TW",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for InvalidLanguage {}
+
+impl FromStr for Language {
+    type Err = InvalidLanguage;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidLanguage(s.to_string());
+        let is_alpha =
+            |tag: &str, len| tag.len() == len && tag.bytes().all(|b| b.is_ascii_alphabetic());
+
+        let mut subtags = s.split('-');
+        let language = subtags.next().ok_or_else(invalid)?;
+        if !is_alpha(language, 2) && !is_alpha(language, 3) {
+            return Err(invalid());
+        }
+        let mut tag = language.to_ascii_lowercase();
+
+        let mut subtag = subtags.next();
+        if let Some(script) = subtag.filter(|script| is_alpha(script, 4)) {
+            tag.push('-');
+            tag.push_str(&script[..1].to_ascii_uppercase());
+            tag.push_str(&script[1..].to_ascii_lowercase());
+            subtag = subtags.next();
+        }
+        if let Some(region) = subtag {
+            let is_numeric = region.len() == 3 && region.bytes().all(|b| b.is_ascii_digit());
+            if !is_alpha(region, 2) && !is_numeric {
+                return Err(invalid());
+            }
+            tag.push('-');
+            tag.push_str(&region.to_ascii_uppercase());
+        }
+        if subtags.next().is_some() {
+            return Err(invalid());
+        }
+
+        Ok(Language(tag))
+    }
+}
+
+impl Serialize for Language {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Language {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// ISO 3166-1 alpha-2 country code, used to localize search results
+///
+/// Codes are normalized to upper case.
+///
+/// ```
+/// use giphy::v1::CountryCode;
+///
+/// let country: CountryCode = "us".parse().unwrap();
+/// assert_eq!(country.as_str(), "US");
+/// assert!("USA".parse::<CountryCode>().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CountryCode([u8; 2]);
+
+impl CountryCode {
+    /// Returns the country code as used by Giphy API
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for CountryCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing a string that is not a valid [`CountryCode`]
+///
+/// [`CountryCode`]: struct.CountryCode.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidCountryCode(pub String);
+
+impl fmt::Display for InvalidCountryCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid country code \"{}\", expected two letters like US",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for InvalidCountryCode {}
+
+impl FromStr for CountryCode {
+    type Err = InvalidCountryCode;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes() {
+            [a, b] if a.is_ascii_alphabetic() && b.is_ascii_alphabetic() => Ok(CountryCode([
+                a.to_ascii_uppercase(),
+                b.to_ascii_uppercase(),
+            ])),
+            _ => Err(InvalidCountryCode(s.to_string())),
+        }
+    }
+}
+
+impl Serialize for CountryCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for CountryCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// [Rendition bundle] limiting the renditions returned for each GIF
+///
+/// [Rendition bundle]: https://developers.giphy.com/docs/optional-settings/#renditions-on-demand
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Bundle {
+    /// Renditions for messaging apps, the default
+    MessagingNonClips,
+    /// Renditions for a grid of clips
+    ClipsGridPicker,
+    /// Renditions for layering stickers over other content
+    StickerLayering,
+    /// Renditions for clients with limited bandwidth
+    LowBandwidth,
+}
+
+impl Bundle {
+    /// Returns the bundle name as used by Giphy API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Bundle::MessagingNonClips => "messaging_non_clips",
+            Bundle::ClipsGridPicker => "clips_grid_picker",
+            Bundle::StickerLayering => "sticker_layering",
+            Bundle::LowBandwidth => "low_bandwidth",
+        }
+    }
+}
+
+impl fmt::Display for Bundle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Per-user identifier issued by the [Random ID endpoint], used to personalize search results
+///
+/// Converts into `String` and `Cow<str>`, so it can be passed to `with_random_id` of search
+/// requests and to analytics reporters.
+///
+/// ```
+/// use giphy::v1::RandomId;
+/// use giphy::v1::gifs::SearchRequest;
+///
+/// let random_id = RandomId::from("e0771ad3f2b64f2d");
+/// let request = SearchRequest::new("tacos").with_random_id(&random_id);
+/// ```
+///
+/// [Random ID endpoint]: https://developers.giphy.com/docs/api/endpoint#random-id
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RandomId(String);
+
+impl RandomId {
+    /// Returns the identifier as used by Giphy API
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RandomId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<String> for RandomId {
+    fn from(random_id: String) -> RandomId {
+        RandomId(random_id)
+    }
+}
+
+impl From<&str> for RandomId {
+    fn from(random_id: &str) -> RandomId {
+        RandomId(random_id.to_string())
+    }
+}
+
+impl From<RandomId> for String {
+    fn from(random_id: RandomId) -> String {
+        random_id.0
+    }
+}
+
+impl From<&RandomId> for String {
+    fn from(random_id: &RandomId) -> String {
+        random_id.0.clone()
+    }
+}
+
+impl From<RandomId> for Cow<'static, str> {
+    fn from(random_id: RandomId) -> Cow<'static, str> {
+        Cow::Owned(random_id.0)
+    }
+}
+
+impl<'a> From<&'a RandomId> for Cow<'a, str> {
+    fn from(random_id: &'a RandomId) -> Cow<'a, str> {
+        Cow::Borrowed(&random_id.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn language_tags() {
+        let parse = |s: &str| s.parse::<Language>().map(|lang| lang.to_string());
+
+        assert_eq!(parse("en"), Ok("en".to_string()));
+        assert_eq!(parse("ES"), Ok("es".to_string()));
+        assert_eq!(parse("zh-tw"), Ok("zh-TW".to_string()));
+        assert_eq!(parse("zh-hant-tw"), Ok("zh-Hant-TW".to_string()));
+        assert_eq!(parse("es-419"), Ok("es-419".to_string()));
+
+        for invalid in &["", "e", "english", "en-", "en-USA", "en-US-x", "e1"] {
+            assert_eq!(parse(invalid), Err(InvalidLanguage(invalid.to_string())));
+        }
+    }
+
+    #[test]
+    fn country_codes() {
+        assert_eq!("us".parse::<CountryCode>().unwrap().as_str(), "US");
+        assert!("".parse::<CountryCode>().is_err());
+        assert!("U1".parse::<CountryCode>().is_err());
+        assert!("USA".parse::<CountryCode>().is_err());
+        assert!(serde_json::from_str::<CountryCode>(r#""pl""#).is_ok());
+        assert!(serde_json::from_str::<CountryCode>(r#""pol""#).is_err());
+    }
+
+    #[test]
+    fn bundles() {
+        assert_eq!(
+            serde_json::to_string(&Bundle::ClipsGridPicker).unwrap(),
+            r#""clips_grid_picker""#
+        );
+        assert_eq!(Bundle::LowBandwidth.to_string(), "low_bandwidth");
+    }
+
+    #[test]
+    fn random_ids() {
+        let random_id: RandomId = serde_json::from_str(r#""e0771ad3f2b64f2d""#).unwrap();
+        assert_eq!(random_id.as_str(), "e0771ad3f2b64f2d");
+        assert_eq!(Cow::from(&random_id), "e0771ad3f2b64f2d");
+        assert_eq!(String::from(random_id), "e0771ad3f2b64f2d");
+    }
+}
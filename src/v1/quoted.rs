@@ -0,0 +1,158 @@
+//! Serde helpers for numbers Giphy API encodes as strings
+//!
+//! Numbers are accepted both quoted and unquoted. Empty strings and `null` are treated
+//! as missing values. Numbers are serialized back as quoted strings, like Giphy does.
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::Serializer;
+use std::convert::TryFrom;
+use std::fmt;
+
+struct QuotedNumber;
+
+impl<'de> Visitor<'de> for QuotedNumber {
+    type Value = Option<u64>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a non-negative integer, possibly quoted")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(Some(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        u64::try_from(value).map(Some).map_err(E::custom)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        match value.trim() {
+            "" => Ok(None),
+            value => value.parse().map(Some).map_err(E::custom),
+        }
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+/// Optional image dimensions, with missing values deserialized as `None`
+pub(crate) mod dimension {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &Option<u32>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.collect_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<u32>, D::Error> {
+        deserializer
+            .deserialize_option(QuotedNumber)?
+            .map(u32::try_from)
+            .transpose()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Optional sizes in bytes, with missing values deserialized as `None`
+pub(crate) mod size {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &Option<u64>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.collect_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<u64>, D::Error> {
+        deserializer.deserialize_option(QuotedNumber)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Image {
+        #[serde(
+            with = "super::dimension",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        width: Option<u32>,
+        #[serde(with = "super::size", default)]
+        size: Option<u64>,
+    }
+
+    fn parse(json: &str) -> Image {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn quoted_and_plain_numbers() {
+        let expected = Image {
+            width: Some(480),
+            size: Some(1024),
+        };
+        assert_eq!(parse(r#"{"width": "480", "size": "1024"}"#), expected);
+        assert_eq!(parse(r#"{"width": 480, "size": 1024}"#), expected);
+    }
+
+    #[test]
+    fn missing_values() {
+        let expected = Image {
+            width: None,
+            size: None,
+        };
+        assert_eq!(parse(r#"{"width": "", "size": ""}"#), expected);
+        assert_eq!(parse(r#"{"width": null, "size": null}"#), expected);
+        assert_eq!(parse(r#"{"width": ""}"#), expected);
+        assert_eq!(parse(r#"{}"#), expected);
+    }
+
+    #[test]
+    fn invalid_values() {
+        assert!(serde_json::from_str::<Image>(r#"{"width": "wide"}"#).is_err());
+        assert!(serde_json::from_str::<Image>(r#"{"width": -1}"#).is_err());
+        assert!(serde_json::from_str::<Image>(r#"{"width": 4294967296}"#).is_err());
+    }
+
+    #[test]
+    fn serialized_quoted() {
+        let image = Image {
+            width: Some(480),
+            size: Some(1024),
+        };
+        assert_eq!(
+            serde_json::to_string(&image).unwrap(),
+            r#"{"width":"480","size":"1024"}"#
+        );
+
+        let image = Image {
+            width: None,
+            size: None,
+        };
+        assert_eq!(serde_json::to_string(&image).unwrap(), r#"{"size":null}"#);
+    }
+}
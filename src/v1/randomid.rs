@@ -0,0 +1,65 @@
+//! Random ID endpoint, issuing per-user identifiers that personalize search results
+//!
+//! The issued [`RandomId`] can be attached to single search requests using `with_random_id`,
+//! or to every request sent through a session created using `SyncApi::session` or
+//! `AsyncApi::session`.
+//!
+//! ```no_run
+//! use giphy::v1::gifs::SearchRequest;
+//! use giphy::v1::randomid::RandomIdRequest;
+//! use giphy::v1::sync::*;
+//!
+//! # let client = reqwest::blocking::Client::new();
+//! # let api = SyncApi::new("[your Giphy API key]".to_string(), client);
+//! let random_id = RandomIdRequest::new().send_to(&api).unwrap().data.random_id;
+//!
+//! let session = api.session(random_id);
+//! let response = SearchRequest::new("tacos").send_to(&session).unwrap();
+//! ```
+//!
+//! [`RandomId`]: ../struct.RandomId.html
+
+use super::model::*;
+
+/// Giphy [Random ID endpoint] request
+///
+/// [Random ID endpoint]: https://developers.giphy.com/docs/api/endpoint#random-id
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RandomIdRequest {}
+
+impl RandomIdRequest {
+    /// Creates new [Random ID] request
+    ///
+    /// [Random ID]: https://developers.giphy.com/docs/api/endpoint#random-id
+    pub fn new() -> RandomIdRequest {
+        Default::default()
+    }
+}
+
+impl GiphyRequest<RandomIdResponse> for RandomIdRequest {
+    fn get_endpoint(&self) -> &str {
+        "v1/randomid"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn random_id_request() {
+        let req = RandomIdRequest::new();
+
+        assert_eq!(req.get_endpoint(), "v1/randomid");
+        assert_eq!(serde_urlencoded::to_string(&req).unwrap(), "");
+    }
+
+    #[test]
+    fn random_id_response() {
+        let response: RandomIdResponse =
+            serde_json::from_str(include_str!("../../data/example-random-id-response.json"))
+                .unwrap();
+
+        assert_eq!(response.data.random_id.as_str(), "e0771ad3f2b64f2d");
+    }
+}
@@ -0,0 +1,416 @@
+//! Selection of the best [`Images`] rendition for given constraints
+//!
+//! Giphy returns each GIF in a number of renditions differing in dimensions, file format
+//! and size. [`RenditionQuery`] describes the constraints of the surface displaying the
+//! GIF, and [`Gif::select_rendition`] picks the best matching [`Rendition`]:
+//!
+//! ```
+//! use giphy::v1::rendition::{Format, RenditionQuery};
+//! use giphy::v1::SingleGifResponse;
+//!
+//! let response: SingleGifResponse =
+//!     serde_json::from_str(include_str!("../../data/example-get-gif-response.json")).unwrap();
+//! let gif = response.data;
+//!
+//! // Animated preview up to 200px wide and 500kB, in a format supported by the client
+//! let query = RenditionQuery::animated()
+//!     .with_max_width(200)
+//!     .with_max_size(500_000)
+//!     .with_formats(&[Format::Webp, Format::Gif]);
+//!
+//! if let Some(rendition) = gif.select_rendition(&query) {
+//!     println!("{} ({:?}x{:?})", rendition.url, rendition.width, rendition.height);
+//! }
+//! ```
+//!
+//! [`Images`]: ../struct.Images.html
+//! [`RenditionQuery`]: struct.RenditionQuery.html
+//! [`Gif::select_rendition`]: ../struct.Gif.html#method.select_rendition
+//! [`Rendition`]: struct.Rendition.html
+
+use std::cmp::Reverse;
+
+use super::model::{Gif, ImageAnimated, ImageStill, Images};
+
+/// File format of a [`Rendition`]
+///
+/// [`Rendition`]: struct.Rendition.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+    Mp4,
+    Webp,
+    Gif,
+    Jpeg,
+}
+
+impl Format {
+    /// Guesses the format from the file extension in given URL
+    fn from_url(url: &str) -> Option<Format> {
+        let path = url.split(['?', '#']).next().unwrap_or(url);
+        let extension = path.rsplit('.').next()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "mp4" => Some(Format::Mp4),
+            "webp" => Some(Format::Webp),
+            "gif" => Some(Format::Gif),
+            "jpg" | "jpeg" => Some(Format::Jpeg),
+            _ => None,
+        }
+    }
+}
+
+/// Single file of a GIF, in specific dimensions and format
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rendition<'a> {
+    /// Name of the rendition in Giphy [`Images`] object, e.g. `fixed_width`
+    ///
+    /// [`Images`]: https://developers.giphy.com/docs/#images-object
+    pub name: &'static str,
+    pub url: &'a str,
+    /// Width in pixels, `None` when unknown
+    pub width: Option<u32>,
+    /// Height in pixels, `None` when unknown
+    pub height: Option<u32>,
+    /// Size in bytes, if returned by Giphy
+    pub size: Option<u64>,
+    pub format: Format,
+    pub still: bool,
+}
+
+/// Constraints used to select a [`Rendition`]
+///
+/// [`Rendition`]: struct.Rendition.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RenditionQuery {
+    still: bool,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+    max_size: Option<u64>,
+    formats: Vec<Format>,
+}
+
+impl RenditionQuery {
+    /// Creates a query for animated renditions, preferring MP4 over WebP over GIF
+    pub fn animated() -> RenditionQuery {
+        RenditionQuery {
+            still: false,
+            max_width: None,
+            max_height: None,
+            max_size: None,
+            formats: vec![Format::Mp4, Format::Webp, Format::Gif],
+        }
+    }
+
+    /// Creates a query for still renditions, preferring WebP over JPEG over GIF
+    pub fn still() -> RenditionQuery {
+        RenditionQuery {
+            still: true,
+            formats: vec![Format::Webp, Format::Jpeg, Format::Gif],
+            ..RenditionQuery::animated()
+        }
+    }
+
+    /// Limits the width of the rendition, in pixels
+    pub fn with_max_width(mut self, max_width: u32) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Limits the height of the rendition, in pixels
+    pub fn with_max_height(mut self, max_height: u32) -> Self {
+        self.max_height = Some(max_height);
+        self
+    }
+
+    /// Limits the size of the rendition file, in bytes
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Specifies acceptable formats, from the most to the least preferred
+    pub fn with_formats(mut self, formats: &[Format]) -> Self {
+        self.formats = formats.to_vec();
+        self
+    }
+
+    /// Returns `true` if the rendition satisfies all constraints
+    ///
+    /// Renditions with unknown dimensions or size don't satisfy the respective limits.
+    pub fn matches(&self, rendition: &Rendition<'_>) -> bool {
+        let within = |value: u64, max: Option<u64>| match max {
+            Some(max) => value > 0 && value <= max,
+            None => true,
+        };
+
+        rendition.still == self.still
+            && self.formats.contains(&rendition.format)
+            && within(
+                rendition.width.map_or(0, u64::from),
+                self.max_width.map(u64::from),
+            )
+            && within(
+                rendition.height.map_or(0, u64::from),
+                self.max_height.map(u64::from),
+            )
+            && within(rendition.size.unwrap_or(0), self.max_size)
+    }
+
+    /// Selects the best rendition satisfying the constraints
+    ///
+    /// Renditions in the most preferred format available are chosen first, then the ones
+    /// with the largest dimensions, then the smallest files.
+    pub fn select<'a>(&self, renditions: &[Rendition<'a>]) -> Option<Rendition<'a>> {
+        renditions
+            .iter()
+            .filter(|rendition| self.matches(rendition))
+            .min_by_key(|rendition| {
+                let preference = self.formats.iter().position(|f| *f == rendition.format);
+                let area =
+                    rendition.width.map_or(0, u64::from) * rendition.height.map_or(0, u64::from);
+                (
+                    preference,
+                    Reverse(area),
+                    rendition.size.unwrap_or(u64::MAX),
+                )
+            })
+            .cloned()
+    }
+}
+
+fn animated<'a>(
+    renditions: &mut Vec<Rendition<'a>>,
+    name: &'static str,
+    image: &'a Option<ImageAnimated>,
+) {
+    let image = match image {
+        Some(image) => image,
+        None => return,
+    };
+    let files = [
+        (image.url.as_deref(), image.size, Format::Gif),
+        (image.mp4.as_deref(), image.mp4_size, Format::Mp4),
+        (image.webp.as_deref(), image.webp_size, Format::Webp),
+    ];
+
+    for (url, size, format) in files.iter() {
+        if let Some(url) = url {
+            renditions.push(Rendition {
+                name,
+                url,
+                width: image.width,
+                height: image.height,
+                size: *size,
+                format: Format::from_url(url).unwrap_or(*format),
+                still: false,
+            });
+        }
+    }
+}
+
+fn still<'a>(
+    renditions: &mut Vec<Rendition<'a>>,
+    name: &'static str,
+    image: &'a Option<ImageStill>,
+) {
+    if let Some(image) = image {
+        renditions.push(Rendition {
+            name,
+            url: &image.url,
+            width: image.width,
+            height: image.height,
+            size: image.size,
+            format: Format::from_url(&image.url).unwrap_or(Format::Gif),
+            still: true,
+        });
+    }
+}
+
+impl Images {
+    /// Returns all renditions known to the library
+    ///
+    /// Renditions missing from the response are skipped.
+    pub fn renditions(&self) -> Vec<Rendition<'_>> {
+        let mut renditions = Vec::new();
+
+        let animated_images = [
+            ("fixed_height", &self.fixed_height),
+            ("fixed_height_downsampled", &self.fixed_height_downsampled),
+            ("fixed_width", &self.fixed_width),
+            ("fixed_width_downsampled", &self.fixed_width_downsampled),
+            ("fixed_height_small", &self.fixed_height_small),
+            ("fixed_width_small", &self.fixed_width_small),
+            ("downsized", &self.downsized),
+            ("downsized_large", &self.downsized_large),
+            ("downsized_medium", &self.downsized_medium),
+            ("downsized_small", &self.downsized_small),
+            ("original", &self.original),
+            ("source", &self.source),
+        ];
+        for (name, image) in animated_images.iter() {
+            animated(&mut renditions, name, image);
+        }
+
+        let still_images = [
+            ("fixed_height_still", &self.fixed_height_still),
+            ("fixed_width_still", &self.fixed_width_still),
+            ("fixed_height_small_still", &self.fixed_height_small_still),
+            ("fixed_width_small_still", &self.fixed_width_small_still),
+            ("downsized_still", &self.downsized_still),
+            ("original_still", &self.original_still),
+            ("480w_still", &self.still_480w),
+        ];
+        for (name, image) in still_images.iter() {
+            still(&mut renditions, name, image);
+        }
+
+        let mp4 = [
+            ("original_mp4", &self.original_mp4),
+            ("preview", &self.preview),
+            ("hd", &self.hd),
+            ("4k", &self.uhd_4k),
+        ];
+        for (name, image) in mp4.iter() {
+            if let Some(image) = image {
+                renditions.push(Rendition {
+                    name,
+                    url: &image.mp4,
+                    width: image.width,
+                    height: image.height,
+                    size: image.mp4_size,
+                    format: Format::Mp4,
+                    still: false,
+                });
+            }
+        }
+
+        let previews = [
+            ("preview_gif", &self.preview_gif, Format::Gif),
+            ("preview_webp", &self.preview_webp, Format::Webp),
+        ];
+        for (name, image, format) in previews.iter() {
+            if let Some(image) = image {
+                renditions.push(Rendition {
+                    name,
+                    url: &image.url,
+                    width: image.width,
+                    height: image.height,
+                    size: image.size,
+                    format: Format::from_url(&image.url).unwrap_or(*format),
+                    still: false,
+                });
+            }
+        }
+
+        if let Some(looping) = &self.looping {
+            renditions.push(Rendition {
+                name: "looping",
+                url: &looping.mp4,
+                width: None,
+                height: None,
+                size: looping.mp4_size,
+                format: Format::Mp4,
+                still: false,
+            });
+        }
+
+        renditions
+    }
+
+    /// Selects the best rendition satisfying given constraints
+    ///
+    /// See [`RenditionQuery::select`] for details.
+    ///
+    /// [`RenditionQuery::select`]: rendition/struct.RenditionQuery.html#method.select
+    pub fn select(&self, query: &RenditionQuery) -> Option<Rendition<'_>> {
+        query.select(&self.renditions())
+    }
+}
+
+impl Gif {
+    /// Selects the best rendition of the GIF satisfying given constraints
+    ///
+    /// See [`RenditionQuery::select`] for details.
+    ///
+    /// [`RenditionQuery::select`]: rendition/struct.RenditionQuery.html#method.select
+    pub fn select_rendition(&self, query: &RenditionQuery) -> Option<Rendition<'_>> {
+        self.images.select(query)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::v1::SingleGifResponse;
+
+    fn gif() -> Gif {
+        let response: SingleGifResponse =
+            serde_json::from_str(include_str!("../../data/example-get-gif-response.json")).unwrap();
+        response.data
+    }
+
+    #[test]
+    fn format_from_url() {
+        assert_eq!(
+            Format::from_url("https://media.giphy.com/200w.webp?cid=abc"),
+            Some(Format::Webp)
+        );
+        assert_eq!(
+            Format::from_url("https://media.giphy.com/480w_s.jpg"),
+            Some(Format::Jpeg)
+        );
+        assert_eq!(Format::from_url("https://media.giphy.com/giphy"), None);
+    }
+
+    #[test]
+    fn prefers_largest_mp4() {
+        let gif = gif();
+        let rendition = gif.select_rendition(&RenditionQuery::animated()).unwrap();
+
+        assert_eq!(rendition.format, Format::Mp4);
+        assert_eq!(rendition.name, "original_mp4");
+        assert_eq!((rendition.width, rendition.height), (Some(480), Some(360)));
+    }
+
+    #[test]
+    fn respects_limits() {
+        let gif = gif();
+        let query = RenditionQuery::animated()
+            .with_max_width(200)
+            .with_max_size(200_000)
+            .with_formats(&[Format::Webp, Format::Gif]);
+        let rendition = gif.select_rendition(&query).unwrap();
+
+        assert_eq!(rendition.format, Format::Webp);
+        assert!(rendition.width.unwrap() <= 200);
+        assert!(rendition.size.unwrap() <= 200_000);
+        assert_eq!(rendition.name, "fixed_width_downsampled");
+    }
+
+    #[test]
+    fn still_renditions() {
+        let gif = gif();
+        let rendition = gif.select_rendition(&RenditionQuery::still()).unwrap();
+        assert!(rendition.still);
+        assert_eq!(rendition.format, Format::Jpeg);
+
+        let query = RenditionQuery::still()
+            .with_formats(&[Format::Gif])
+            .with_max_height(100);
+        let rendition = gif.select_rendition(&query).unwrap();
+        assert_eq!(rendition.name, "fixed_height_small_still");
+    }
+
+    #[test]
+    fn falls_back_when_renditions_missing() {
+        let mut gif = gif();
+        gif.images.original_mp4 = None;
+        gif.images.original = None;
+
+        let rendition = gif.select_rendition(&RenditionQuery::animated()).unwrap();
+        assert_eq!(rendition.format, Format::Mp4);
+        assert_eq!(rendition.name, "downsized_small");
+
+        let query = RenditionQuery::animated().with_max_size(1000);
+        assert_eq!(gif.select_rendition(&query), None);
+    }
+}
@@ -0,0 +1,363 @@
+use super::model::*;
+use super::pagination::PaginatedRequest;
+use super::params::*;
+use std::borrow::Cow;
+use std::default::Default;
+use std::time::Duration;
+
+/// Giphy [Sticker Search endpoint] request
+///
+/// [Sticker Search endpoint]: https://developers.giphy.com/docs/api/endpoint#sticker-search
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SearchRequest<'p> {
+    #[serde(rename = "q")]
+    pub(crate) query: Cow<'p, str>,
+
+    pub(crate) limit: Option<u32>,
+
+    pub(crate) offset: Option<u32>,
+
+    #[serde(serialize_with = "serialize_filter_rating")]
+    pub(crate) rating: Option<Rating>,
+
+    pub(crate) lang: Option<Language>,
+
+    pub(crate) random_id: Option<Cow<'p, str>>,
+
+    pub(crate) bundle: Option<Bundle>,
+
+    pub(crate) country_code: Option<CountryCode>,
+}
+
+impl<'p> SearchRequest<'p> {
+    /// Creates new [Sticker Search endpoint] request
+    ///
+    /// [Sticker Search endpoint]: https://developers.giphy.com/docs/api/endpoint#sticker-search
+    pub fn new(query: impl Into<Cow<'p, str>>) -> SearchRequest<'p> {
+        SearchRequest {
+            query: query.into(),
+            limit: None,
+            offset: None,
+            rating: None,
+            lang: None,
+            random_id: None,
+            bundle: None,
+            country_code: None,
+        }
+    }
+
+    /// Limits the maximum number of sticker objects returned from [Sticker Search] request
+    ///
+    /// [Sticker Search]: https://developers.giphy.com/docs/api/endpoint#sticker-search
+    pub fn with_limit(mut self, value: u32) -> Self {
+        self.limit = Some(value);
+        self
+    }
+
+    /// Specifies the number of sticker objects to skip when making [Sticker Search] request
+    ///
+    /// [Sticker Search]: https://developers.giphy.com/docs/api/endpoint#sticker-search
+    pub fn with_offset(mut self, value: u32) -> Self {
+        self.offset = Some(value);
+        self
+    }
+
+    /// Specifies the rating of sticker objects returned from [Sticker Search] request
+    ///
+    /// [Sticker Search]: https://developers.giphy.com/docs/api/endpoint#sticker-search
+    pub fn with_rating(mut self, value: Rating) -> Self {
+        self.rating = Some(value);
+        self
+    }
+
+    /// Specifies the language of the query of [Sticker Search] request
+    ///
+    /// [Sticker Search]: https://developers.giphy.com/docs/api/endpoint#sticker-search
+    pub fn with_lang(mut self, value: Language) -> Self {
+        self.lang = Some(value);
+        self
+    }
+
+    /// Specifies the random ID of the user making [Sticker Search] request
+    ///
+    /// [Sticker Search]: https://developers.giphy.com/docs/api/endpoint#sticker-search
+    pub fn with_random_id(mut self, value: impl Into<Cow<'p, str>>) -> Self {
+        self.random_id = Some(value.into());
+        self
+    }
+
+    /// Specifies the rendition bundle of sticker objects returned from [Sticker Search] request
+    ///
+    /// [Sticker Search]: https://developers.giphy.com/docs/api/endpoint#sticker-search
+    pub fn with_bundle(mut self, value: Bundle) -> Self {
+        self.bundle = Some(value);
+        self
+    }
+
+    /// Localizes the results of [Sticker Search] request to given country
+    ///
+    /// [Sticker Search]: https://developers.giphy.com/docs/api/endpoint#sticker-search
+    pub fn with_country_code(mut self, value: CountryCode) -> Self {
+        self.country_code = Some(value);
+        self
+    }
+
+    /// Converts the request into one owning its query, which can outlive the borrowed data
+    pub fn into_owned(self) -> SearchRequest<'static> {
+        SearchRequest {
+            query: Cow::Owned(self.query.into_owned()),
+            limit: self.limit,
+            offset: self.offset,
+            rating: self.rating,
+            lang: self.lang,
+            random_id: self.random_id.map(|id| Cow::Owned(id.into_owned())),
+            bundle: self.bundle,
+            country_code: self.country_code,
+        }
+    }
+}
+
+impl<'p> GiphyRequest<PaginatedGifListResponse> for SearchRequest<'p> {
+    fn get_endpoint(&self) -> &'static str {
+        "v1/stickers/search"
+    }
+}
+
+impl<'p> PaginatedRequest for SearchRequest<'p> {
+    fn offset(&self) -> u32 {
+        self.offset.unwrap_or(0)
+    }
+
+    fn set_offset(&mut self, offset: u32) {
+        self.offset = Some(offset);
+    }
+
+    fn limit(&self) -> Option<u32> {
+        self.limit
+    }
+}
+
+/// Giphy [Sticker Trending endpoint] request
+///
+/// [Sticker Trending endpoint]: https://developers.giphy.com/docs/api/endpoint#sticker-trending
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TrendingRequest {
+    #[serde(serialize_with = "serialize_filter_rating")]
+    pub(crate) rating: Option<Rating>,
+
+    pub(crate) limit: Option<u32>,
+
+    pub(crate) offset: Option<u32>,
+}
+
+impl TrendingRequest {
+    /// Creates new [Sticker Trending endpoint] request
+    ///
+    /// [Sticker Trending endpoint]: https://developers.giphy.com/docs/api/endpoint#sticker-trending
+    pub fn new() -> TrendingRequest {
+        Default::default()
+    }
+
+    /// Specifies the rating of sticker objects returned from [Sticker Trending] request
+    ///
+    /// [Sticker Trending]: https://developers.giphy.com/docs/api/endpoint#sticker-trending
+    pub fn with_rating(mut self, rating: Rating) -> Self {
+        self.rating = Some(rating);
+        self
+    }
+
+    /// Limits the maximum number of sticker objects returned from [Sticker Trending] request
+    ///
+    /// [Sticker Trending]: https://developers.giphy.com/docs/api/endpoint#sticker-trending
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Specifies the number of sticker objects to skip when making [Sticker Trending] request
+    ///
+    /// [Sticker Trending]: https://developers.giphy.com/docs/api/endpoint#sticker-trending
+    pub fn with_offset(mut self, offset: u32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+impl GiphyRequest<PaginatedGifListResponse> for TrendingRequest {
+    fn get_endpoint(&self) -> &'static str {
+        "v1/stickers/trending"
+    }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(Duration::from_secs(10 * 60))
+    }
+}
+
+impl PaginatedRequest for TrendingRequest {
+    fn offset(&self) -> u32 {
+        self.offset.unwrap_or(0)
+    }
+
+    fn set_offset(&mut self, offset: u32) {
+        self.offset = Some(offset);
+    }
+
+    fn limit(&self) -> Option<u32> {
+        self.limit
+    }
+}
+
+/// Giphy [Sticker Translate endpoint] request
+///
+/// [Sticker Translate endpoint]: https://developers.giphy.com/docs/api/endpoint#sticker-translate
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TranslateRequest<'a> {
+    #[serde(rename = "s")]
+    pub(crate) phrase: Cow<'a, str>,
+
+    pub(crate) weirdness: Option<u8>,
+}
+
+impl<'a> TranslateRequest<'a> {
+    /// Creates new [Sticker Translate endpoint] request
+    ///
+    /// [Sticker Translate endpoint]: https://developers.giphy.com/docs/api/endpoint#sticker-translate
+    pub fn new(phrase: impl Into<Cow<'a, str>>) -> TranslateRequest<'a> {
+        TranslateRequest {
+            phrase: phrase.into(),
+            weirdness: None,
+        }
+    }
+
+    /// Specifies the weirdness value for [Sticker Translate] request
+    ///
+    /// [Sticker Translate]: https://developers.giphy.com/docs/api/endpoint#sticker-translate
+    pub fn with_weirdness(mut self, value: u8) -> Self {
+        self.weirdness = Some(value);
+        self
+    }
+
+    /// Converts the request into one owning its phrase, which can outlive the borrowed data
+    pub fn into_owned(self) -> TranslateRequest<'static> {
+        TranslateRequest {
+            phrase: Cow::Owned(self.phrase.into_owned()),
+            weirdness: self.weirdness,
+        }
+    }
+}
+
+impl<'p> GiphyRequest<SingleGifResponse> for TranslateRequest<'p> {
+    fn get_endpoint(&self) -> &'static str {
+        "v1/stickers/translate"
+    }
+}
+
+/// Giphy [Sticker Random endpoint] request
+///
+/// [Sticker Random endpoint]: https://developers.giphy.com/docs/api/endpoint#sticker-random
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RandomRequest<'a> {
+    pub(crate) tag: Option<Cow<'a, str>>,
+
+    #[serde(serialize_with = "serialize_filter_rating")]
+    pub(crate) rating: Option<Rating>,
+}
+
+impl<'a> RandomRequest<'a> {
+    /// Creates new [Sticker Random endpoint] request
+    ///
+    /// [Sticker Random endpoint]: https://developers.giphy.com/docs/api/endpoint#sticker-random
+    pub fn new() -> RandomRequest<'a> {
+        Default::default()
+    }
+
+    /// Filters [Sticker Random] request by specific tag
+    ///
+    /// [Sticker Random]: https://developers.giphy.com/docs/api/endpoint#sticker-random
+    pub fn with_tag(mut self, value: impl Into<Cow<'a, str>>) -> Self {
+        self.tag = Some(value.into());
+        self
+    }
+
+    /// Filters [Sticker Random] request by specific rating
+    ///
+    /// [Sticker Random]: https://developers.giphy.com/docs/api/endpoint#sticker-random
+    pub fn with_rating(mut self, value: Rating) -> Self {
+        self.rating = Some(value);
+        self
+    }
+
+    /// Converts the request into one owning its tag, which can outlive the borrowed data
+    pub fn into_owned(self) -> RandomRequest<'static> {
+        RandomRequest {
+            tag: self.tag.map(|tag| Cow::Owned(tag.into_owned())),
+            rating: self.rating,
+        }
+    }
+}
+
+impl<'a> GiphyRequest<SingleGifResponse> for RandomRequest<'a> {
+    fn get_endpoint(&self) -> &'static str {
+        "v1/stickers/random"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn search_request() {
+        let req = SearchRequest::new("hello").with_limit(100).with_offset(5);
+
+        assert_eq!(req.get_endpoint(), "v1/stickers/search");
+        assert_eq!(req.query, "hello");
+        assert_eq!(req.limit, Some(100));
+        assert_eq!(req.offset, Some(5));
+    }
+
+    #[test]
+    fn search_request_query() {
+        let req = SearchRequest::new("hello")
+            .with_offset(5)
+            .with_lang("es".parse().unwrap())
+            .with_bundle(Bundle::StickerLayering);
+        assert_eq!(
+            serde_urlencoded::to_string(&req).unwrap(),
+            "q=hello&offset=5&lang=es&bundle=sticker_layering"
+        );
+    }
+
+    #[test]
+    fn trending_request() {
+        let req = TrendingRequest::new()
+            .with_rating(Rating::G)
+            .with_limit(100)
+            .with_offset(5);
+
+        assert_eq!(req.get_endpoint(), "v1/stickers/trending");
+        assert_eq!(req.rating, Some(Rating::G));
+        assert_eq!(req.limit, Some(100));
+        assert_eq!(req.offset, Some(5));
+    }
+
+    #[test]
+    fn translate_request() {
+        let req = TranslateRequest::new("rage").with_weirdness(10);
+
+        assert_eq!(req.get_endpoint(), "v1/stickers/translate");
+        assert_eq!(req.phrase, "rage");
+        assert_eq!(req.weirdness, Some(10));
+    }
+
+    #[test]
+    fn random_request() {
+        let req = RandomRequest::new()
+            .with_tag("burrito")
+            .with_rating(Rating::G);
+
+        assert_eq!(req.get_endpoint(), "v1/stickers/random");
+        assert_eq!(req.tag.as_deref(), Some("burrito"));
+        assert_eq!(req.rating, Some(Rating::G));
+    }
+}
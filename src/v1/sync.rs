@@ -1,28 +1,246 @@
 use serde::de::DeserializeOwned;
+use std::thread;
+use std::time::Duration;
 
-use super::model::{GiphyRequest, API_ROOT};
+use super::analytics::AnalyticsReporter;
+use super::categories::{
+    next_offset, CategoriesRequest, CategoryListRequest, SubcategoriesRequest, MAX_DEPTH,
+};
+use super::channels::ChannelGifsRequest;
+use super::model::{
+    ApiHost, Category, CategoryListResponse, Gif, GiphyRequest, PaginatedGifListResponse, API_ROOT,
+    UPLOAD_ROOT,
+};
+use super::pagination::{PaginatedRequest, Paginator};
+use super::params::RandomId;
+use crate::cache::ResponseCache;
+use crate::error::{decode_response, handle_response, GiphyError};
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
+use crate::transport::{HttpRequest, Method, Transport};
 
-/// Implementation of Giphy API that uses synchronous [`reqwest::Client`]
+/// Implementation of Giphy API that uses a synchronous [`Transport`]
 ///
-/// [`reqwest::Client`]: https://docs.rs/reqwest/0.11.4/reqwest/blocking/struct.Client.html
-pub struct SyncApi {
+/// With the default `reqwest` feature, the transport is [`reqwest::blocking::Client`].
+///
+/// [`Transport`]: ../../transport/trait.Transport.html
+/// [`reqwest::blocking::Client`]: https://docs.rs/reqwest/0.11.4/reqwest/blocking/struct.Client.html
+pub struct SyncApi<
+    #[cfg(feature = "reqwest")] T = reqwest::blocking::Client,
+    #[cfg(not(feature = "reqwest"))] T,
+> {
     url: String,
+    upload_url: String,
     key: String,
-    client: reqwest::blocking::Client,
+    transport: T,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
+    random_id: Option<RandomId>,
 }
 
-impl SyncApi {
+/// [`SyncApi`] session created using [`SyncApi::session`], sending a `random_id` with every request
+///
+/// [`SyncApi`]: ./struct.SyncApi.html
+/// [`SyncApi::session`]: ./struct.SyncApi.html#method.session
+pub type SyncSession<'a, T> = SyncApi<&'a T>;
+
+impl<T: Transport> SyncApi<T> {
     /// Creates a new synchronous Giphy API Client
-    pub fn new(key: String, client: reqwest::blocking::Client) -> SyncApi {
+    pub fn new(key: String, transport: T) -> SyncApi<T> {
+        SyncApi::new_with_url(API_ROOT.to_string(), key, transport)
+            .with_upload_url(UPLOAD_ROOT.to_string())
+    }
+
+    /// Creates a new synchronous Giphy API Client with a custom API root
+    ///
+    /// Useful for testing against API mocks. Uploads are sent to the same API root, unless
+    /// changed using `with_upload_url`.
+    pub fn new_with_url(url: String, key: String, transport: T) -> SyncApi<T> {
         SyncApi {
-            url: API_ROOT.to_string(),
+            upload_url: url.clone(),
+            url,
             key,
-            client,
+            transport,
+            retry_policy: None,
+            rate_limiter: None,
+            cache: None,
+            random_id: None,
+        }
+    }
+
+    /// Sends uploads to a custom upload API root
+    pub fn with_upload_url(mut self, upload_url: String) -> Self {
+        self.upload_url = upload_url;
+        self
+    }
+
+    /// Retries failed `GET` requests according to given [`RetryPolicy`]
+    ///
+    /// Uploads are never retried, since a failed response does not mean the upload failed.
+    ///
+    /// [`RetryPolicy`]: ../../struct.RetryPolicy.html
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Limits the rate of requests made to Giphy API using given [`RateLimiter`]
+    ///
+    /// [`RateLimiter`]: ../../struct.RateLimiter.html
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Caches responses using given [`ResponseCache`]
+    ///
+    /// [`ResponseCache`]: ../../cache/struct.ResponseCache.html
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Returns the [`RateLimiter`] attached to the client, if there is one
+    ///
+    /// [`RateLimiter`]: ../../struct.RateLimiter.html
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Creates a session borrowing the client, which sends every request with given
+    /// `random_id`
+    ///
+    /// The session shares the transport, retry policy, rate limiter and cache of the client.
+    /// Requests that set their own `random_id` keep it.
+    pub fn session(&self, random_id: impl Into<RandomId>) -> SyncSession<'_, T> {
+        SyncApi {
+            url: self.url.clone(),
+            upload_url: self.upload_url.clone(),
+            key: self.key.clone(),
+            transport: &self.transport,
+            retry_policy: self.retry_policy.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+            cache: self.cache.clone(),
+            random_id: Some(random_id.into()),
+        }
+    }
+
+    /// Returns the `random_id` sent with every request, if the client is a session
+    pub fn random_id(&self) -> Option<&RandomId> {
+        self.random_id.as_ref()
+    }
+
+    /// Creates an [`AnalyticsReporter`] sending analytics pingbacks for the user with given
+    /// `random_id`
+    ///
+    /// [`AnalyticsReporter`]: ../analytics/struct.AnalyticsReporter.html
+    pub fn analytics_reporter(&self, random_id: impl Into<String>) -> AnalyticsReporter<'_, T> {
+        AnalyticsReporter::new(&self.transport, random_id.into())
+    }
+
+    /// Walks all pages of a [`PaginatedRequest`], yielding GIFs one by one
+    ///
+    /// Pages are fetched lazily starting from the request offset, until all results are
+    /// returned or Giphy [`MAX_OFFSET`] is reached. GIFs already returned on previous
+    /// pages are skipped.
+    ///
+    /// [`PaginatedRequest`]: ../pagination/trait.PaginatedRequest.html
+    /// [`MAX_OFFSET`]: ../pagination/constant.MAX_OFFSET.html
+    pub fn paginate<R: PaginatedRequest>(&self, request: R) -> PaginatedIter<'_, R, T> {
+        PaginatedIter {
+            api: self,
+            pages: Paginator::new(request),
+        }
+    }
+
+    /// Fetches all GIFs of the channel with given id, walking all pages of a
+    /// [`ChannelGifsRequest`]
+    ///
+    /// [`ChannelGifsRequest`]: ../channels/struct.ChannelGifsRequest.html
+    pub fn channel_gifs(&self, channel_id: u64) -> Result<Vec<Gif>, GiphyError> {
+        self.paginate(ChannelGifsRequest::new(channel_id)).collect()
+    }
+
+    /// Fetches Giphy categories, walking the category tree to given depth
+    ///
+    /// Depth `1` returns top-level categories, with subcategories listed by name only.
+    /// Depth `2` also fetches subcategories of each category, including their featured GIFs.
+    /// Depths greater than [`MAX_DEPTH`] are the same as `MAX_DEPTH`. All pages of each
+    /// category list are fetched.
+    ///
+    /// [`MAX_DEPTH`]: ../categories/constant.MAX_DEPTH.html
+    pub fn category_tree(&self, depth: usize) -> Result<Vec<Category>, GiphyError> {
+        if depth == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut categories = self.all_categories(CategoriesRequest::new())?;
+        if depth >= MAX_DEPTH {
+            for category in &mut categories {
+                let request = SubcategoriesRequest::new(&category.name_encoded);
+                category.subcategories = self.all_categories(request)?;
+            }
+        }
+        Ok(categories)
+    }
+
+    fn all_categories<R: CategoryListRequest>(
+        &self,
+        mut request: R,
+    ) -> Result<Vec<Category>, GiphyError> {
+        let mut categories = Vec::new();
+        loop {
+            let page: CategoryListResponse = request.send_to(self)?;
+            let next = next_offset(&page);
+            categories.extend(page.data);
+            match next {
+                Some(offset) => request.set_offset(offset),
+                None => return Ok(categories),
+            }
         }
     }
 
-    pub fn new_with_url(url: String, key: String, client: reqwest::blocking::Client) -> SyncApi {
-        SyncApi { url, key, client }
+    fn send<ResponseType>(
+        &self,
+        request: HttpRequest,
+        ttl: Option<Duration>,
+    ) -> Result<ResponseType, GiphyError>
+    where
+        ResponseType: DeserializeOwned,
+    {
+        let cache = self.cache.as_ref().zip(ttl);
+        if let Some(body) = cache.and_then(|(cache, _)| cache.get(&request)) {
+            return decode_response(body);
+        }
+
+        let mut attempt = 1;
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire_blocking()?;
+            }
+
+            let result = self
+                .transport
+                .send(&request)
+                .map_err(GiphyError::Transport)
+                .and_then(|response| match cache {
+                    Some((cache, ttl)) => cache.handle_response(&request, response, ttl),
+                    None => handle_response(response),
+                });
+
+            let delay = match (&result, &self.retry_policy) {
+                (Err(e), Some(policy)) if request.method == Method::Get => {
+                    policy.retry_delay(attempt, e)
+                }
+                _ => None,
+            };
+            match delay {
+                Some(delay) => thread::sleep(delay),
+                None => return result,
+            }
+            attempt += 1;
+        }
     }
 }
 
@@ -30,31 +248,74 @@ pub trait RunnableSyncRequest<ResponseType> {
     /// Sends a request to a [SyncApi]
     ///
     /// [SyncApi]: ./struct.SyncApi.html
-    fn send_to(&self, api: &SyncApi) -> Result<ResponseType, reqwest::Error>;
+    fn send_to<T: Transport>(&self, api: &SyncApi<T>) -> Result<ResponseType, GiphyError>;
 }
 
-impl<'a, RequestType, ResponseType> RunnableSyncRequest<ResponseType> for RequestType
+impl<RequestType, ResponseType> RunnableSyncRequest<ResponseType> for RequestType
 where
     RequestType: GiphyRequest<ResponseType>,
     ResponseType: DeserializeOwned,
 {
-    fn send_to(&self, api: &SyncApi) -> Result<ResponseType, reqwest::Error> {
-        let endpoint = format!("{}/{}", api.url, self.get_endpoint());
+    fn send_to<T: Transport>(&self, api: &SyncApi<T>) -> Result<ResponseType, GiphyError> {
+        let api_root = match self.host() {
+            ApiHost::Api => &api.url,
+            ApiHost::Upload => &api.upload_url,
+        };
+        let request = HttpRequest::new(api_root, &api.key, self.get_endpoint(), self)?
+            .without_params(self.path_params())
+            .with_body(self.method(), self.body())
+            .with_random_id(api.random_id.as_ref().map(RandomId::as_str));
+        let ttl = api
+            .cache
+            .as_ref()
+            .filter(|_| request.method == Method::Get)
+            .and_then(|cache| cache.ttl_for(self.get_endpoint(), self.cache_ttl()));
+        api.send(request, ttl)
+    }
+}
+
+/// Iterator over GIFs from all pages of a [`PaginatedRequest`]
+///
+/// Created using [`SyncApi::paginate`]. Iteration stops after the first error.
+///
+/// [`PaginatedRequest`]: ../pagination/trait.PaginatedRequest.html
+/// [`SyncApi::paginate`]: ./struct.SyncApi.html#method.paginate
+pub struct PaginatedIter<'a, R, T> {
+    api: &'a SyncApi<T>,
+    pages: Paginator<R>,
+}
 
-        let response = api
-            .client
-            .get(&endpoint)
-            .query(&[("api_key", api.key.clone())])
-            .query(&self)
-            .send()?;
+impl<'a, R: PaginatedRequest, T: Transport> PaginatedIter<'a, R, T> {
+    /// Limits the total number of GIFs returned by the iterator
+    pub fn with_max_items(mut self, max_items: usize) -> Self {
+        self.pages.set_max_items(max_items);
+        self
+    }
+}
+
+impl<'a, R: PaginatedRequest, T: Transport> Iterator for PaginatedIter<'a, R, T> {
+    type Item = Result<Gif, GiphyError>;
 
-        let response_data = response.error_for_status()?.json::<ResponseType>()?;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(gif) = self.pages.next_buffered() {
+                return Some(Ok(gif));
+            }
 
-        Ok(response_data)
+            let request = self.pages.next_request()?;
+            let response: Result<PaginatedGifListResponse, _> = request.send_to(self.api);
+            match response {
+                Ok(page) => self.pages.accept_page(page),
+                Err(e) => {
+                    self.pages.finish();
+                    return Some(Err(e));
+                }
+            }
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod test {
     use dotenv::dotenv;
     use mockito::{mock, server_url, Matcher};
@@ -62,6 +323,20 @@ mod test {
 
     use super::*;
     use crate::v1;
+    use crate::v2;
+    use crate::{RateLimitMode, RateLimiter, RetryPolicy};
+    use std::time::Duration;
+
+    fn search_page(offset: u32, first_id: u32, total_count: u32) -> String {
+        let mut page: serde_json::Value =
+            serde_json::from_str(include_str!("../../data/example-search-response.json")).unwrap();
+        for (i, gif) in page["data"].as_array_mut().unwrap().iter_mut().enumerate() {
+            gif["id"] = format!("gif{}", first_id + i as u32).into();
+        }
+        page["pagination"]["offset"] = offset.into();
+        page["pagination"]["total_count"] = total_count.into();
+        page.to_string()
+    }
 
     #[test]
     fn api_search_200_ok() {
@@ -183,6 +458,42 @@ mod test {
         assert!(response.meta.status == 200);
     }
 
+    #[test]
+    fn api_get_gif_id_not_in_query() {
+        let m = mock("GET", "/v1/gifs/path-id-sync")
+            .match_query(Matcher::Regex("^api_key=[^&]+$".to_string()))
+            .with_status(200)
+            .with_body_from_file("data/example-get-gif-response.json")
+            .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(server_url(), "key".to_string(), client);
+
+        v1::gifs::GetGifRequest::new("path-id-sync")
+            .send_to(&api)
+            .unwrap();
+        m.assert();
+    }
+
+    #[test]
+    fn api_lenient_get_gif_id_not_in_query() {
+        let m = mock("GET", "/v1/gifs/lenient-path-id-sync")
+            .match_query(Matcher::Regex("^api_key=[^&]+$".to_string()))
+            .with_status(200)
+            .with_body_from_file("data/example-get-gif-response.json")
+            .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(server_url(), "key".to_string(), client);
+
+        let response: v1::lenient::LenientGifResponse =
+            v1::lenient::Lenient::new(v1::gifs::GetGifRequest::new("lenient-path-id-sync"))
+                .send_to(&api)
+                .unwrap();
+        assert!(response.data.is_some());
+        m.assert();
+    }
+
     #[test]
     fn api_get_gifs_200_ok() {
         dotenv().ok();
@@ -207,4 +518,630 @@ mod test {
 
         assert!(response.meta.status == 200);
     }
+
+    #[test]
+    fn api_stickers_search_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/stickers/search.*api_key=.+q=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-stickers-search-response.json")
+        .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::stickers::SearchRequest::new("rage")
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while calling sticker search endpoint: {:?}", e));
+
+        assert!(response.pagination.count > 0);
+        assert!(response.data.iter().all(|s| s.gif_type == "sticker"));
+    }
+
+    #[test]
+    fn api_stickers_trending_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/stickers/trending.*api_key=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-stickers-trending-response.json")
+        .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::stickers::TrendingRequest::new()
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while calling sticker trending endpoint: {:?}", e));
+
+        assert!(response.pagination.count > 0);
+    }
+
+    #[test]
+    fn api_stickers_translate_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/stickers/translate.*api_key=.+&s=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-stickers-translate-response.json")
+        .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::stickers::TranslateRequest::new("rage")
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while calling sticker translate endpoint: {:?}", e));
+
+        assert!(response.meta.status == 200);
+    }
+
+    #[test]
+    fn api_stickers_random_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/stickers/random.*api_key=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-stickers-random-response.json")
+        .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::stickers::RandomRequest::new()
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while calling sticker random endpoint: {:?}", e));
+
+        assert!(response.meta.status == 200);
+    }
+
+    #[test]
+    fn api_related_tags_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/tags/related/rage.*api_key=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-related-tags-response.json")
+        .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::tags::RelatedTagsRequest::new("rage")
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while calling related tags endpoint: {:?}", e));
+
+        assert_eq!(response.data[0].name, "angry");
+    }
+
+    #[test]
+    fn api_autocomplete_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/gifs/search/tags.*api_key=.+q=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-autocomplete-response.json")
+        .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::tags::AutocompleteRequest::new("rag")
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while calling autocomplete endpoint: {:?}", e));
+
+        assert!(!response.data.is_empty());
+    }
+
+    #[test]
+    fn api_trending_searches_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/trending/searches.*api_key=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-trending-searches-response.json")
+        .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::tags::TrendingSearchesRequest::new()
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while calling trending searches endpoint: {:?}", e));
+
+        assert!(!response.data.is_empty());
+    }
+
+    #[test]
+    fn api_random_id_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock("GET", Matcher::Regex(r"/randomid.*api_key=.+".to_string()))
+            .with_status(200)
+            .with_body_from_file("data/example-random-id-response.json")
+            .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v1::randomid::RandomIdRequest::new()
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while calling random ID endpoint: {:?}", e));
+
+        assert_eq!(response.data.random_id.as_str(), "e0771ad3f2b64f2d");
+    }
+
+    #[test]
+    fn api_session_sends_random_id() {
+        let session_search = mock("GET", "/v1/gifs/search")
+            .match_query(Matcher::Regex(
+                "^api_key=[^&]+&q=tacos&random_id=e0771ad3f2b64f2d$".to_string(),
+            ))
+            .with_status(200)
+            .with_body_from_file("data/example-search-response.json")
+            .expect(1)
+            .create();
+        let own_search = mock("GET", "/v1/gifs/search")
+            .match_query(Matcher::Regex(
+                "^api_key=[^&]+&q=tacos&random_id=own$".to_string(),
+            ))
+            .with_status(200)
+            .with_body_from_file("data/example-search-response.json")
+            .expect(1)
+            .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(server_url(), "key".to_string(), client);
+        let random_id = v1::RandomId::from("e0771ad3f2b64f2d");
+        let session = api.session(random_id.clone());
+        assert_eq!(api.random_id(), None);
+        assert_eq!(session.random_id(), Some(&random_id));
+
+        v1::gifs::SearchRequest::new("tacos")
+            .send_to(&session)
+            .unwrap();
+        v1::gifs::SearchRequest::new("tacos")
+            .with_random_id("own")
+            .send_to(&session)
+            .unwrap();
+
+        session_search.assert();
+        own_search.assert();
+    }
+
+    #[test]
+    fn api_emoji_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/v2/emoji\?.*api_key=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-emoji-response.json")
+        .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v2::emoji::EmojiRequest::new()
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while calling emoji endpoint: {:?}", e));
+
+        assert_eq!(response.pagination.next_cursor, Some(3));
+    }
+
+    #[test]
+    fn api_emoji_variations_200_ok() {
+        dotenv().ok();
+        let api_key = env::var("GIPHY_API_KEY_TEST").unwrap_or_else(|_| "test-api-key".to_string());
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/v2/emoji/13EjnL7RwHmA2Q/variations.*api_key=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-emoji-variations-response.json")
+        .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, api_key, client);
+
+        let response = v2::emoji::EmojiVariationsRequest::new("13EjnL7RwHmA2Q")
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while calling emoji variations endpoint: {:?}", e));
+
+        assert_eq!(response.data.len(), 2);
+    }
+
+    #[test]
+    fn api_category_tree() {
+        let api_root = server_url();
+        let _categories = mock("GET", "/v1/gifs/categories")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_body_from_file("data/example-categories-response.json")
+            .expect(2)
+            .create();
+        let subcategories = mock(
+            "GET",
+            Matcher::Regex(r"^/v1/gifs/categories/\w+\?".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-subcategories-response.json")
+        .expect(2)
+        .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, "test-api-key".to_string(), client);
+
+        assert!(api.category_tree(0).unwrap().is_empty());
+
+        let categories = api.category_tree(1).unwrap();
+        assert_eq!(categories.len(), 2);
+        assert!(categories[0].subcategories[0].gif.is_none());
+
+        let categories = api.category_tree(3).unwrap();
+        assert_eq!(categories.len(), 2);
+        assert!(categories
+            .iter()
+            .flat_map(|category| &category.subcategories)
+            .all(|subcategory| subcategory.gif.is_some()));
+        subcategories.assert();
+    }
+
+    #[test]
+    fn api_channels_search_200_ok() {
+        let api_root = server_url();
+        let _m = mock("GET", "/v1/channels/search")
+            .match_query(Matcher::UrlEncoded(
+                "q".to_string(),
+                "the office".to_string(),
+            ))
+            .with_status(200)
+            .with_body_from_file("data/example-channels-search-response.json")
+            .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, "test-api-key".to_string(), client);
+
+        let response = v1::channels::ChannelSearchRequest::new("the office")
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while calling channel search endpoint: {:?}", e));
+
+        assert_eq!(response.data[0].display_name, "The Office");
+    }
+
+    #[test]
+    fn api_channel_gifs() {
+        let api_root = server_url();
+        let _first = mock("GET", "/v1/channels/3142/gifs")
+            .match_query(Matcher::Regex("^api_key=[^&]+$".to_string()))
+            .with_status(200)
+            .with_body(search_page(0, 0, 7))
+            .create();
+        let _second = mock("GET", "/v1/channels/3142/gifs")
+            .match_query(Matcher::UrlEncoded("offset".to_string(), "5".to_string()))
+            .with_status(200)
+            .with_body(search_page(5, 2, 7))
+            .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, "test-api-key".to_string(), client);
+
+        let ids: Vec<String> = api
+            .channel_gifs(3142)
+            .unwrap_or_else(|e| panic!("Error while listing channel GIFs: {:?}", e))
+            .into_iter()
+            .map(|gif| gif.id)
+            .collect();
+
+        let expected: Vec<String> = (0..7).map(|i| format!("gif{}", i)).collect();
+        assert_eq!(ids, expected);
+    }
+
+    #[test]
+    fn api_upload_200_ok() {
+        let api_root = server_url();
+        let _m = mock("POST", "/v1/gifs")
+            .match_query(Matcher::UrlEncoded(
+                "api_key".to_string(),
+                "test-api-key".to_string(),
+            ))
+            .match_header(
+                "content-type",
+                Matcher::Regex("^multipart/form-data; boundary=".to_string()),
+            )
+            .match_body(Matcher::AllOf(vec![
+                Matcher::Regex(r#"name="file"; filename="tacos.gif"\r\n"#.to_string()),
+                Matcher::Regex("GIF89a".to_string()),
+                Matcher::Regex("tacos,food".to_string()),
+            ]))
+            .with_status(200)
+            .with_body_from_file("data/example-upload-response.json")
+            .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, "test-api-key".to_string(), client);
+
+        let response = v1::upload::UploadRequest::from_bytes("tacos.gif", &b"GIF89a"[..])
+            .with_tags(vec!["tacos", "food"])
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while calling upload endpoint: {:?}", e));
+
+        assert_eq!(response.data.id, "3o7TKMt1VVNkHV2PaE");
+    }
+
+    #[test]
+    fn api_upload_uses_upload_url() {
+        let _m = mock("POST", "/v1/gifs")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_body_from_file("data/example-upload-response.json")
+            .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api =
+            SyncApi::new_with_url("http://127.0.0.1:9".to_string(), "key".to_string(), client)
+                .with_upload_url(server_url());
+
+        let response = v1::upload::UploadRequest::from_url("https://example.com/tacos.gif")
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while calling upload endpoint: {:?}", e));
+
+        assert_eq!(response.data.id, "3o7TKMt1VVNkHV2PaE");
+    }
+
+    #[test]
+    fn api_paginate_search() {
+        let api_root = server_url();
+        let _first = mock("GET", "/v1/gifs/search")
+            .match_query(Matcher::UrlEncoded("offset".to_string(), "0".to_string()))
+            .with_status(200)
+            .with_body(search_page(0, 0, 8))
+            .create();
+        let _second = mock("GET", "/v1/gifs/search")
+            .match_query(Matcher::UrlEncoded("offset".to_string(), "5".to_string()))
+            .with_status(200)
+            .with_body(search_page(5, 3, 8))
+            .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, "test-api-key".to_string(), client);
+
+        let ids = api
+            .paginate(v1::gifs::SearchRequest::new("rage").with_offset(0))
+            .map(|gif| gif.map(|g| g.id))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_or_else(|e| panic!("Error while paginating search endpoint: {:?}", e));
+
+        let expected: Vec<String> = (0..8).map(|i| format!("gif{}", i)).collect();
+        assert_eq!(ids, expected);
+    }
+
+    #[test]
+    fn api_paginate_max_items() {
+        let api_root = server_url();
+        let m = mock("GET", "/v1/gifs/trending")
+            .match_query(Matcher::UrlEncoded("offset".to_string(), "0".to_string()))
+            .with_status(200)
+            .with_body(search_page(0, 0, 100))
+            .expect(1)
+            .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, "test-api-key".to_string(), client);
+
+        let gifs = api
+            .paginate(v1::gifs::TrendingRequest::new().with_offset(0))
+            .with_max_items(3)
+            .collect::<Vec<_>>();
+
+        assert_eq!(gifs.len(), 3);
+        m.assert();
+    }
+
+    #[test]
+    fn api_retry_until_success() {
+        let api_root = server_url();
+        let failing = mock("GET", "/v1/gifs/retry-sync?api_key=test-api-key")
+            .with_status(503)
+            .expect(2)
+            .create();
+        let rate_limited = mock("GET", "/v1/gifs/retry-sync?api_key=test-api-key")
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .expect(1)
+            .create();
+        let ok = mock("GET", "/v1/gifs/retry-sync?api_key=test-api-key")
+            .with_status(200)
+            .with_body_from_file("data/example-get-gif-response.json")
+            .expect(1)
+            .create();
+
+        let client = reqwest::blocking::Client::new();
+        let policy = RetryPolicy::new()
+            .with_max_attempts(4)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(5));
+        let api = SyncApi::new_with_url(api_root, "test-api-key".to_string(), client)
+            .with_retry_policy(policy);
+
+        let response = v1::gifs::GetGifRequest::new("retry-sync")
+            .send_to(&api)
+            .unwrap_or_else(|e| panic!("Error while retrying get gif endpoint: {:?}", e));
+
+        assert!(response.meta.status == 200);
+        failing.assert();
+        rate_limited.assert();
+        ok.assert();
+    }
+
+    #[test]
+    fn api_retry_gives_up() {
+        let api_root = server_url();
+        let failing = mock("GET", "/v1/gifs/retry-sync-fail?api_key=test-api-key")
+            .with_status(500)
+            .expect(3)
+            .create();
+
+        let client = reqwest::blocking::Client::new();
+        let policy = RetryPolicy::new()
+            .with_max_attempts(3)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(5));
+        let api = SyncApi::new_with_url(api_root, "test-api-key".to_string(), client)
+            .with_retry_policy(policy);
+
+        let err = v1::gifs::GetGifRequest::new("retry-sync-fail")
+            .send_to(&api)
+            .unwrap_err();
+
+        assert_eq!(err.status(), Some(500));
+        failing.assert();
+    }
+
+    #[test]
+    fn api_rate_limiter_fail_fast() {
+        let api_root = server_url();
+        let m = mock("GET", "/v1/gifs/limited-sync?api_key=test-api-key")
+            .with_status(200)
+            .with_body_from_file("data/example-get-gif-response.json")
+            .expect(1)
+            .create();
+
+        let client = reqwest::blocking::Client::new();
+        let limiter = RateLimiter::per_hour(1).with_mode(RateLimitMode::FailFast);
+        let api = SyncApi::new_with_url(api_root, "test-api-key".to_string(), client)
+            .with_rate_limiter(limiter);
+
+        let request = v1::gifs::GetGifRequest::new("limited-sync");
+        assert!(request.send_to(&api).is_ok());
+        assert_eq!(api.rate_limiter().map(|l| l.remaining()), Some(0));
+
+        match request.send_to(&api) {
+            Err(GiphyError::RateLimited { meta: None, .. }) => (),
+            r => panic!("Unexpected result: {:?}", r),
+        }
+        m.assert();
+    }
+
+    #[test]
+    fn api_cache_serves_repeated_requests() {
+        let api_root = server_url();
+        let ok = mock(
+            "GET",
+            Matcher::Regex(r"^/v1/gifs/cache-sync\?api_key=.+".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-get-gif-response.json")
+        .expect(1)
+        .create();
+
+        let cache = ResponseCache::in_memory(10);
+        let api = SyncApi::new_with_url(
+            api_root.clone(),
+            "first-key".to_string(),
+            reqwest::blocking::Client::new(),
+        )
+        .with_cache(cache.clone());
+        let other_key_api = SyncApi::new_with_url(
+            api_root,
+            "second-key".to_string(),
+            reqwest::blocking::Client::new(),
+        )
+        .with_cache(cache);
+
+        for api in [&api, &other_key_api, &api] {
+            let response = v1::gifs::GetGifRequest::new("cache-sync")
+                .send_to(api)
+                .unwrap_or_else(|e| panic!("Error while calling get gif endpoint: {:?}", e));
+            assert_eq!(response.data.id, "xT4uQulxzV39haRFjG");
+        }
+        ok.assert();
+    }
+
+    #[test]
+    fn api_cache_skips_uncached_requests() {
+        let api_root = server_url();
+        let ok = mock(
+            "GET",
+            Matcher::Regex(r"^/v1/gifs/search\?.*q=nocache".to_string()),
+        )
+        .with_status(200)
+        .with_body_from_file("data/example-search-response.json")
+        .expect(2)
+        .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, "test-api-key".to_string(), client)
+            .with_cache(ResponseCache::in_memory(10));
+
+        for _ in 0..2 {
+            v1::gifs::SearchRequest::new("nocache")
+                .send_to(&api)
+                .unwrap_or_else(|e| panic!("Error while calling search endpoint: {:?}", e));
+        }
+        ok.assert();
+    }
+
+    #[test]
+    fn api_lenient_search() {
+        let mut body: serde_json::Value =
+            serde_json::from_str(include_str!("../../data/example-search-response.json")).unwrap();
+        body["data"][0]["images"] = "broken".into();
+        body["data"][1] = serde_json::Value::Null;
+
+        let api_root = server_url();
+        let _m = mock(
+            "GET",
+            Matcher::Regex(r"/gifs/search.*q=lenient".to_string()),
+        )
+        .with_status(200)
+        .with_body(body.to_string())
+        .create();
+
+        let client = reqwest::blocking::Client::new();
+        let api = SyncApi::new_with_url(api_root, "test-api-key".to_string(), client);
+
+        let strict: Result<v1::PaginatedGifListResponse, _> =
+            v1::gifs::SearchRequest::new("lenient").send_to(&api);
+        assert!(strict.is_err());
+
+        let response: v1::lenient::LenientGifListResponse =
+            v1::lenient::Lenient::new(v1::gifs::SearchRequest::new("lenient"))
+                .send_to(&api)
+                .unwrap();
+        assert_eq!(response.data.len(), 4);
+        assert_eq!(response.warnings.len(), 2);
+    }
 }
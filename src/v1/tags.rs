@@ -0,0 +1,222 @@
+//! Tag endpoints, useful for building search suggestions
+//!
+//! ```no_run
+//! use giphy::v1::tags::{AutocompleteRequest, RelatedTagsRequest};
+//! use giphy::v1::sync::*;
+//!
+//! # let client = reqwest::blocking::Client::new();
+//! # let api = SyncApi::new("[your Giphy API key]".to_string(), client);
+//! let suggestions = AutocompleteRequest::new("rag").with_limit(5).send_to(&api).unwrap();
+//! let related = RelatedTagsRequest::new("rage").send_to(&api).unwrap();
+//!
+//! for tag in suggestions.data.iter().chain(&related.data) {
+//!     println!("{}", tag.name);
+//! }
+//! ```
+
+use super::model::*;
+use std::borrow::Cow;
+use std::time::Duration;
+
+/// Giphy [Related Tags endpoint] request
+///
+/// [Related Tags endpoint]: https://developers.giphy.com/docs/api/endpoint#related-tags
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "RelatedTagsParams")]
+pub struct RelatedTagsRequest {
+    pub(crate) term: String,
+
+    #[serde(skip)]
+    pub(crate) endpoint: String,
+}
+
+/// Serialized form of [`RelatedTagsRequest`], from which the endpoint is rebuilt
+#[derive(Deserialize)]
+struct RelatedTagsParams {
+    term: String,
+}
+
+impl From<RelatedTagsParams> for RelatedTagsRequest {
+    fn from(params: RelatedTagsParams) -> RelatedTagsRequest {
+        RelatedTagsRequest::new(&params.term)
+    }
+}
+
+impl RelatedTagsRequest {
+    /// Creates new [Related Tags] request for tags related to given term
+    ///
+    /// [Related Tags]: https://developers.giphy.com/docs/api/endpoint#related-tags
+    pub fn new(term: &str) -> RelatedTagsRequest {
+        RelatedTagsRequest {
+            term: term.to_string(),
+            endpoint: format!("v1/tags/related/{}", encode_path_segment(term)),
+        }
+    }
+}
+
+impl GiphyRequest<TagListResponse> for RelatedTagsRequest {
+    fn get_endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    fn path_params(&self) -> &'static [&'static str] {
+        &["term"]
+    }
+}
+
+/// Giphy [Search Suggestions endpoint] request, autocompleting a search query
+///
+/// [Search Suggestions endpoint]: https://developers.giphy.com/docs/api/endpoint#search-suggestions
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AutocompleteRequest<'q> {
+    #[serde(rename = "q")]
+    pub(crate) query: Cow<'q, str>,
+
+    pub(crate) limit: Option<u32>,
+
+    pub(crate) offset: Option<u32>,
+}
+
+impl<'q> AutocompleteRequest<'q> {
+    /// Creates new [Search Suggestions] request for given partial query
+    ///
+    /// [Search Suggestions]: https://developers.giphy.com/docs/api/endpoint#search-suggestions
+    pub fn new(query: impl Into<Cow<'q, str>>) -> AutocompleteRequest<'q> {
+        AutocompleteRequest {
+            query: query.into(),
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// Limits the maximum number of tags returned from [Search Suggestions] request
+    ///
+    /// [Search Suggestions]: https://developers.giphy.com/docs/api/endpoint#search-suggestions
+    pub fn with_limit(mut self, value: u32) -> Self {
+        self.limit = Some(value);
+        self
+    }
+
+    /// Specifies the number of tags to skip when making [Search Suggestions] request
+    ///
+    /// [Search Suggestions]: https://developers.giphy.com/docs/api/endpoint#search-suggestions
+    pub fn with_offset(mut self, value: u32) -> Self {
+        self.offset = Some(value);
+        self
+    }
+
+    /// Converts the request into one owning its query, which can outlive the borrowed data
+    pub fn into_owned(self) -> AutocompleteRequest<'static> {
+        AutocompleteRequest {
+            query: Cow::Owned(self.query.into_owned()),
+            limit: self.limit,
+            offset: self.offset,
+        }
+    }
+}
+
+impl<'q> GiphyRequest<TagListResponse> for AutocompleteRequest<'q> {
+    fn get_endpoint(&self) -> &str {
+        "v1/gifs/search/tags"
+    }
+}
+
+/// Giphy [Trending Search Terms endpoint] request
+///
+/// [Trending Search Terms endpoint]: https://developers.giphy.com/docs/api/endpoint#trending-search-terms
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TrendingSearchesRequest {}
+
+impl TrendingSearchesRequest {
+    /// Creates new [Trending Search Terms] request
+    ///
+    /// [Trending Search Terms]: https://developers.giphy.com/docs/api/endpoint#trending-search-terms
+    pub fn new() -> TrendingSearchesRequest {
+        Default::default()
+    }
+}
+
+impl GiphyRequest<TrendingSearchesResponse> for TrendingSearchesRequest {
+    fn get_endpoint(&self) -> &str {
+        "v1/trending/searches"
+    }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(Duration::from_secs(10 * 60))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn related_tags_request() {
+        let req = RelatedTagsRequest::new("rage");
+        assert_eq!(req.get_endpoint(), "v1/tags/related/rage");
+
+        let req = RelatedTagsRequest::new("rage quit/ü?");
+        assert_eq!(
+            req.get_endpoint(),
+            "v1/tags/related/rage%20quit%2F%C3%BC%3F"
+        );
+        assert_eq!(req.path_params(), &["term"]);
+    }
+
+    #[test]
+    fn deserialize_related_tags_request() {
+        let req = RelatedTagsRequest::new("rage quit/ü?");
+        let json = serde_json::to_string(&req).unwrap();
+        let req: RelatedTagsRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(req.term, "rage quit/ü?");
+        assert_eq!(
+            req.get_endpoint(),
+            "v1/tags/related/rage%20quit%2F%C3%BC%3F"
+        );
+    }
+
+    #[test]
+    fn autocomplete_request() {
+        let req = AutocompleteRequest::new("rag")
+            .with_limit(5)
+            .with_offset(10);
+
+        assert_eq!(req.get_endpoint(), "v1/gifs/search/tags");
+        assert_eq!(
+            serde_urlencoded::to_string(&req).unwrap(),
+            "q=rag&limit=5&offset=10"
+        );
+    }
+
+    #[test]
+    fn trending_searches_request() {
+        let req = TrendingSearchesRequest::new();
+
+        assert_eq!(req.get_endpoint(), "v1/trending/searches");
+        assert_eq!(serde_urlencoded::to_string(&req).unwrap(), "");
+    }
+
+    #[test]
+    fn tag_responses() {
+        let related: TagListResponse = serde_json::from_str(include_str!(
+            "../../data/example-related-tags-response.json"
+        ))
+        .unwrap();
+        assert_eq!(related.data[0].name, "angry");
+        assert!(related.data[0].analytics_response_payload.is_some());
+        assert!(related.pagination.is_none());
+
+        let suggestions: TagListResponse = serde_json::from_str(include_str!(
+            "../../data/example-autocomplete-response.json"
+        ))
+        .unwrap();
+        assert_eq!(suggestions.data.len(), 3);
+        assert_eq!(suggestions.pagination.map(|p| p.count), Some(3));
+
+        let trending: TrendingSearchesResponse = serde_json::from_str(include_str!(
+            "../../data/example-trending-searches-response.json"
+        ))
+        .unwrap();
+        assert_eq!(trending.data[0], "happy friday");
+    }
+}
@@ -0,0 +1,377 @@
+//! GIF upload endpoint
+//!
+//! Uploads are sent as `multipart/form-data` `POST` requests to Giphy upload API, which is
+//! hosted at [`UPLOAD_ROOT`] instead of the regular API root. GIFs can be uploaded from
+//! a local file, from bytes in memory, or fetched by Giphy from a source URL.
+//!
+//! ```no_run
+//! use giphy::v1::sync::*;
+//! use giphy::v1::upload::UploadRequest;
+//!
+//! # let client = reqwest::blocking::Client::new();
+//! # let api = SyncApi::new("[your Giphy API key]".to_string(), client);
+//! let response = UploadRequest::from_file("tacos.gif")
+//!     .unwrap()
+//!     .with_tags(vec!["tacos", "food"])
+//!     .with_username("taco_lover")
+//!     .send_to(&api)
+//!     .unwrap();
+//!
+//! println!("Uploaded GIF {}", response.data.id);
+//! ```
+//!
+//! [`UPLOAD_ROOT`]: ../static.UPLOAD_ROOT.html
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::model::*;
+use crate::transport::{Method, RequestBody};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Source of the uploaded GIF
+#[derive(Clone)]
+enum UploadSource {
+    File { name: String, bytes: Vec<u8> },
+    Url(String),
+}
+
+impl fmt::Debug for UploadSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UploadSource::File { name, bytes } => f
+                .debug_struct("File")
+                .field("name", name)
+                .field("len", &bytes.len())
+                .finish(),
+            UploadSource::Url(url) => f.debug_tuple("Url").field(url).finish(),
+        }
+    }
+}
+
+/// Giphy [Upload endpoint] request
+///
+/// [Upload endpoint]: https://developers.giphy.com/docs/api/endpoint#upload
+#[derive(Clone, Debug, Serialize)]
+pub struct UploadRequest {
+    #[serde(skip)]
+    source: UploadSource,
+
+    #[serde(skip)]
+    pub(crate) tags: Vec<String>,
+
+    #[serde(skip)]
+    pub(crate) source_post_url: Option<String>,
+
+    #[serde(skip)]
+    pub(crate) username: Option<String>,
+}
+
+impl UploadRequest {
+    /// Creates new [Upload] request uploading the file at given path
+    ///
+    /// [Upload]: https://developers.giphy.com/docs/api/endpoint#upload
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<UploadRequest> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let bytes = fs::read(path)?;
+
+        Ok(UploadRequest::from_bytes(name, bytes))
+    }
+
+    /// Creates new [Upload] request uploading given file contents
+    ///
+    /// The file name is sent to Giphy along with the contents.
+    ///
+    /// [Upload]: https://developers.giphy.com/docs/api/endpoint#upload
+    pub fn from_bytes<N, B>(file_name: N, bytes: B) -> UploadRequest
+    where
+        N: Into<String>,
+        B: Into<Vec<u8>>,
+    {
+        UploadRequest::new(UploadSource::File {
+            name: file_name.into(),
+            bytes: bytes.into(),
+        })
+    }
+
+    /// Creates new [Upload] request for a GIF Giphy fetches from given URL
+    ///
+    /// [Upload]: https://developers.giphy.com/docs/api/endpoint#upload
+    pub fn from_url<U: Into<String>>(source_image_url: U) -> UploadRequest {
+        UploadRequest::new(UploadSource::Url(source_image_url.into()))
+    }
+
+    fn new(source: UploadSource) -> UploadRequest {
+        UploadRequest {
+            source,
+            tags: Vec::new(),
+            source_post_url: None,
+            username: None,
+        }
+    }
+
+    /// Tags the GIF uploaded by [Upload] request
+    ///
+    /// [Upload]: https://developers.giphy.com/docs/api/endpoint#upload
+    pub fn with_tags<I, T>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.tags.extend(tags.into_iter().map(Into::into));
+        self
+    }
+
+    /// Specifies the URL of the page the GIF uploaded by [Upload] request comes from
+    ///
+    /// [Upload]: https://developers.giphy.com/docs/api/endpoint#upload
+    pub fn with_source_post_url<U: Into<String>>(mut self, url: U) -> Self {
+        self.source_post_url = Some(url.into());
+        self
+    }
+
+    /// Specifies the channel username the GIF is uploaded to by [Upload] request
+    ///
+    /// Only available to channel owners with production API keys.
+    ///
+    /// [Upload]: https://developers.giphy.com/docs/api/endpoint#upload
+    pub fn with_username<U: Into<String>>(mut self, username: U) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    /// Derives the multipart boundary from a hash of the request fields
+    ///
+    /// Unlike a random boundary, this keeps the body of the same upload identical between
+    /// runs, so recorded uploads can be replayed from a cassette.
+    fn boundary(&self) -> String {
+        let mut hash = FNV_OFFSET_BASIS;
+        let mut field = |bytes: &[u8]| {
+            // 0xff never occurs in UTF-8, separating the fields unambiguously
+            for &byte in bytes.iter().chain(&[0xff]) {
+                hash = (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+            }
+        };
+
+        field(self.username.as_deref().unwrap_or_default().as_bytes());
+        match &self.source {
+            UploadSource::File { name, bytes } => {
+                field(name.as_bytes());
+                field(bytes);
+            }
+            UploadSource::Url(url) => field(url.as_bytes()),
+        }
+        field(self.tags.join(",").as_bytes());
+        field(
+            self.source_post_url
+                .as_deref()
+                .unwrap_or_default()
+                .as_bytes(),
+        );
+
+        format!("giphy-rs-{:016x}", hash)
+    }
+
+    /// Encodes the request fields as a multipart form using given boundary
+    fn encode(&self, boundary: &str) -> RequestBody {
+        let mut form = Multipart::new(boundary);
+        if let Some(username) = &self.username {
+            form.text("username", username);
+        }
+        match &self.source {
+            UploadSource::File { name, bytes } => form.file("file", name, bytes),
+            UploadSource::Url(url) => form.text("source_image_url", url),
+        }
+        if !self.tags.is_empty() {
+            form.text("tags", &self.tags.join(","));
+        }
+        if let Some(url) = &self.source_post_url {
+            form.text("source_post_url", url);
+        }
+
+        form.finish()
+    }
+}
+
+impl GiphyRequest<UploadResponse> for UploadRequest {
+    fn get_endpoint(&self) -> &str {
+        "v1/gifs"
+    }
+
+    fn host(&self) -> ApiHost {
+        ApiHost::Upload
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn body(&self) -> Option<RequestBody> {
+        Some(self.encode(&self.boundary()))
+    }
+}
+
+/// `multipart/form-data` body encoder
+struct Multipart {
+    boundary: String,
+    bytes: Vec<u8>,
+}
+
+impl Multipart {
+    fn new(boundary: &str) -> Multipart {
+        Multipart {
+            boundary: boundary.to_string(),
+            bytes: Vec::new(),
+        }
+    }
+
+    fn text(&mut self, name: &str, value: &str) {
+        self.part(format!("Content-Disposition: form-data; name=\"{}\"", name));
+        self.bytes.extend_from_slice(value.as_bytes());
+        self.bytes.extend_from_slice(b"\r\n");
+    }
+
+    fn file(&mut self, name: &str, file_name: &str, contents: &[u8]) {
+        self.part(format!(
+            "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}",
+            name,
+            file_name.replace(['"', '\r', '\n'], "_"),
+            content_type(file_name)
+        ));
+        self.bytes.extend_from_slice(contents);
+        self.bytes.extend_from_slice(b"\r\n");
+    }
+
+    fn part(&mut self, headers: String) {
+        self.bytes.extend_from_slice(b"--");
+        self.bytes.extend_from_slice(self.boundary.as_bytes());
+        self.bytes.extend_from_slice(b"\r\n");
+        self.bytes.extend_from_slice(headers.as_bytes());
+        self.bytes.extend_from_slice(b"\r\n\r\n");
+    }
+
+    fn finish(mut self) -> RequestBody {
+        self.bytes.extend_from_slice(b"--");
+        self.bytes.extend_from_slice(self.boundary.as_bytes());
+        self.bytes.extend_from_slice(b"--\r\n");
+
+        RequestBody {
+            content_type: format!("multipart/form-data; boundary={}", self.boundary),
+            bytes: self.bytes,
+        }
+    }
+}
+
+/// Guesses the content type of an uploaded file from its extension
+fn content_type(file_name: &str) -> &'static str {
+    let extension = file_name.rsplit('.').next().unwrap_or_default();
+    match extension.to_ascii_lowercase().as_str() {
+        "gif" => "image/gif",
+        "mp4" => "video/mp4",
+        "mov" => "video/quicktime",
+        "webm" => "video/webm",
+        "webp" => "image/webp",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn upload_request() {
+        let req = UploadRequest::from_url("https://example.com/tacos.gif");
+
+        assert_eq!(req.get_endpoint(), "v1/gifs");
+        assert_eq!(req.host(), ApiHost::Upload);
+        assert_eq!(req.method(), Method::Post);
+        assert_eq!(serde_urlencoded::to_string(&req).unwrap(), "");
+
+        let body = req.body().unwrap();
+        assert!(body
+            .content_type
+            .starts_with("multipart/form-data; boundary=giphy-rs-"));
+    }
+
+    #[test]
+    fn boundary_follows_content() {
+        let req = UploadRequest::from_url("https://example.com/tacos.gif");
+        assert_eq!(req.body(), req.clone().body());
+
+        let other = req.clone().with_tags(vec!["tacos"]);
+        assert_ne!(req.boundary(), other.boundary());
+
+        let file = UploadRequest::from_bytes("tacos.gif", b"GIF89a".to_vec());
+        let other = UploadRequest::from_bytes("tacos.gif", b"GIF87a".to_vec());
+        assert_ne!(file.boundary(), other.boundary());
+    }
+
+    #[test]
+    fn encode_url_upload() {
+        let body = UploadRequest::from_url("https://example.com/tacos.gif")
+            .with_tags(vec!["tacos", "food"])
+            .with_source_post_url("https://example.com/post")
+            .with_username("taco_lover")
+            .encode("boundary");
+
+        assert_eq!(body.content_type, "multipart/form-data; boundary=boundary");
+        assert_eq!(
+            String::from_utf8(body.bytes).unwrap(),
+            "--boundary\r\n\
+             Content-Disposition: form-data; name=\"username\"\r\n\r\n\
+             taco_lover\r\n\
+             --boundary\r\n\
+             Content-Disposition: form-data; name=\"source_image_url\"\r\n\r\n\
+             https://example.com/tacos.gif\r\n\
+             --boundary\r\n\
+             Content-Disposition: form-data; name=\"tags\"\r\n\r\n\
+             tacos,food\r\n\
+             --boundary\r\n\
+             Content-Disposition: form-data; name=\"source_post_url\"\r\n\r\n\
+             https://example.com/post\r\n\
+             --boundary--\r\n"
+        );
+    }
+
+    #[test]
+    fn encode_file_upload() {
+        let path = env::temp_dir().join(format!("giphy-upload-{}.gif", fastrand::u64(..)));
+        fs::write(&path, b"GIF89a").unwrap();
+
+        let body = UploadRequest::from_file(&path).unwrap().encode("b");
+        let name = path.file_name().unwrap().to_str().unwrap();
+        assert_eq!(
+            String::from_utf8(body.bytes).unwrap(),
+            format!(
+                "--b\r\n\
+                 Content-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\n\
+                 Content-Type: image/gif\r\n\r\n\
+                 GIF89a\r\n\
+                 --b--\r\n",
+                name
+            )
+        );
+
+        fs::remove_file(&path).unwrap();
+        assert!(UploadRequest::from_file(&path).is_err());
+    }
+
+    #[test]
+    fn file_content_types() {
+        assert_eq!(content_type("tacos.GIF"), "image/gif");
+        assert_eq!(content_type("tacos.mp4"), "video/mp4");
+        assert_eq!(content_type("tacos"), "application/octet-stream");
+    }
+}
@@ -0,0 +1,198 @@
+//! Emoji endpoints
+//!
+//! The emoji list is paginated using cursors. Each response carries the cursor of the next
+//! page, which [`EmojiRequest::next_page`] turns into the request for that page.
+//!
+//! ```no_run
+//! use giphy::v1::sync::*;
+//! use giphy::v2::emoji::EmojiRequest;
+//! use giphy::v2::EmojiListResponse;
+//!
+//! # let client = reqwest::blocking::Client::new();
+//! # let api = SyncApi::new("[your Giphy API key]".to_string(), client);
+//! let mut request = Some(EmojiRequest::new().with_limit(50));
+//! while let Some(page) = request {
+//!     let response: EmojiListResponse = page.send_to(&api).unwrap();
+//!     for emoji in &response.data {
+//!         println!("{}", emoji.title);
+//!     }
+//!     request = page.next_page(&response);
+//! }
+//! ```
+//!
+//! [`EmojiRequest::next_page`]: struct.EmojiRequest.html#method.next_page
+
+use super::model::*;
+use crate::v1::{encode_path_segment, GiphyRequest};
+use std::time::Duration;
+
+/// Giphy [Emoji endpoint] request
+///
+/// [Emoji endpoint]: https://developers.giphy.com/docs/api/endpoint#emoji
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EmojiRequest {
+    pub(crate) limit: Option<u32>,
+
+    #[serde(rename = "offset")]
+    pub(crate) cursor: Option<u32>,
+}
+
+impl EmojiRequest {
+    /// Creates new [Emoji endpoint] request for the first page of emoji
+    ///
+    /// [Emoji endpoint]: https://developers.giphy.com/docs/api/endpoint#emoji
+    pub fn new() -> EmojiRequest {
+        Default::default()
+    }
+
+    /// Limits the maximum number of emoji returned from [Emoji] request
+    ///
+    /// [Emoji]: https://developers.giphy.com/docs/api/endpoint#emoji
+    pub fn with_limit(mut self, value: u32) -> Self {
+        self.limit = Some(value);
+        self
+    }
+
+    /// Starts [Emoji] request at given cursor, as returned in `next_cursor` of a previous page
+    ///
+    /// [Emoji]: https://developers.giphy.com/docs/api/endpoint#emoji
+    pub fn with_cursor(mut self, value: u32) -> Self {
+        self.cursor = Some(value);
+        self
+    }
+
+    /// Returns the request for the page following given response, or `None` on the last page
+    pub fn next_page(&self, response: &EmojiListResponse) -> Option<EmojiRequest> {
+        let next_cursor = response.pagination.next_cursor?;
+        if response.data.is_empty() || next_cursor <= self.cursor.unwrap_or(0) {
+            return None;
+        }
+        Some(self.clone().with_cursor(next_cursor))
+    }
+}
+
+impl GiphyRequest<EmojiListResponse> for EmojiRequest {
+    fn get_endpoint(&self) -> &str {
+        "v2/emoji"
+    }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(Duration::from_secs(24 * 60 * 60))
+    }
+}
+
+/// Giphy [Emoji Variations endpoint] request
+///
+/// [Emoji Variations endpoint]: https://developers.giphy.com/docs/api/endpoint#emoji-variations
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "EmojiVariationsParams")]
+pub struct EmojiVariationsRequest {
+    pub(crate) emoji_id: String,
+
+    #[serde(skip)]
+    pub(crate) endpoint: String,
+}
+
+/// Serialized form of [`EmojiVariationsRequest`], from which the endpoint is rebuilt
+#[derive(Deserialize)]
+struct EmojiVariationsParams {
+    emoji_id: String,
+}
+
+impl From<EmojiVariationsParams> for EmojiVariationsRequest {
+    fn from(params: EmojiVariationsParams) -> EmojiVariationsRequest {
+        EmojiVariationsRequest::new(&params.emoji_id)
+    }
+}
+
+impl EmojiVariationsRequest {
+    /// Creates new [Emoji Variations] request for the emoji with given id
+    ///
+    /// [Emoji Variations]: https://developers.giphy.com/docs/api/endpoint#emoji-variations
+    pub fn new(emoji_id: &str) -> EmojiVariationsRequest {
+        EmojiVariationsRequest {
+            emoji_id: emoji_id.to_string(),
+            endpoint: format!("v2/emoji/{}/variations", encode_path_segment(emoji_id)),
+        }
+    }
+}
+
+impl GiphyRequest<EmojiVariationsResponse> for EmojiVariationsRequest {
+    fn get_endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(Duration::from_secs(24 * 60 * 60))
+    }
+
+    fn path_params(&self) -> &'static [&'static str] {
+        &["emoji_id"]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn emoji_response() -> EmojiListResponse {
+        serde_json::from_str(include_str!("../../data/example-emoji-response.json")).unwrap()
+    }
+
+    #[test]
+    fn emoji_request() {
+        let req = EmojiRequest::new().with_limit(3).with_cursor(6);
+
+        assert_eq!(req.get_endpoint(), "v2/emoji");
+        assert_eq!(
+            serde_urlencoded::to_string(&req).unwrap(),
+            "limit=3&offset=6"
+        );
+    }
+
+    #[test]
+    fn emoji_next_page() {
+        let req = EmojiRequest::new().with_limit(3);
+        let mut response = emoji_response();
+        assert_eq!(response.pagination.next_cursor, Some(3));
+
+        let next = req.next_page(&response).unwrap();
+        assert_eq!(next.cursor, Some(3));
+        assert_eq!(next.limit, Some(3));
+
+        assert!(next.next_page(&response).is_none());
+
+        response.pagination.next_cursor = None;
+        assert!(req.next_page(&response).is_none());
+    }
+
+    #[test]
+    fn emoji_variations_request() {
+        let req = EmojiVariationsRequest::new("dalJ0CpF7hwmN1nZXe");
+
+        assert_eq!(req.get_endpoint(), "v2/emoji/dalJ0CpF7hwmN1nZXe/variations");
+        assert_eq!(req.path_params(), &["emoji_id"]);
+    }
+
+    #[test]
+    fn deserialize_emoji_variations_request() {
+        let req = EmojiVariationsRequest::new("dalJ0CpF7hwmN1nZXe");
+        let json = serde_json::to_string(&req).unwrap();
+        let req: EmojiVariationsRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(req.emoji_id, "dalJ0CpF7hwmN1nZXe");
+        assert_eq!(req.get_endpoint(), "v2/emoji/dalJ0CpF7hwmN1nZXe/variations");
+    }
+
+    #[test]
+    fn emoji_responses() {
+        let response = emoji_response();
+        assert_eq!(response.data.len(), 3);
+        assert_eq!(response.data[0].gif_type, "emoji");
+
+        let response: EmojiVariationsResponse = serde_json::from_str(include_str!(
+            "../../data/example-emoji-variations-response.json"
+        ))
+        .unwrap();
+        assert_eq!(response.data.len(), 2);
+    }
+}
@@ -0,0 +1,12 @@
+//! [Giphy API v2] wrapper for Rust
+//!
+//! Requests from this module work with the `v1` synchronous and asynchronous clients,
+//! and return the `v1` [`Gif`] objects.
+//!
+//! [Giphy API v2]: https://developers.giphy.com/docs/api/endpoint#emoji
+//! [`Gif`]: ../v1/struct.Gif.html
+
+pub mod emoji;
+mod model;
+
+pub use model::*;
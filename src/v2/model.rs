@@ -0,0 +1,31 @@
+use crate::v1::{Gif, Meta};
+
+/// Giphy v2 cursor pagination object representation
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CursorPagination {
+    pub count: i32,
+    pub offset: i32,
+    /// Cursor of the next page, `None` on the last page
+    #[serde(default)]
+    pub next_cursor: Option<u32>,
+}
+
+/// Emoji list response object representation
+///
+/// Returned by the [Emoji](https://developers.giphy.com/docs/api/endpoint#emoji) endpoint.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EmojiListResponse {
+    pub data: Vec<Gif>,
+    pub pagination: CursorPagination,
+    pub meta: Meta,
+}
+
+/// Emoji variations response object representation
+///
+/// Returned by the [Emoji Variations](https://developers.giphy.com/docs/api/endpoint#emoji-variations)
+/// endpoint.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EmojiVariationsResponse {
+    pub data: Vec<Gif>,
+    pub meta: Meta,
+}